@@ -0,0 +1,341 @@
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use serde_json::Value;
+
+use crate::adapter::{scalar_to_string, template_name, ResolvedTemplate, TemplateAdapter};
+use crate::config::schema::{TemplateConfig, TemplateMetadata};
+use crate::config::variable::{Choice, VariableConfig, VariableType};
+use crate::error::{DicecutError, Result};
+
+pub const CONFIG_FILE: &str = "cookiecutter.json";
+
+/// Name under which Cookiecutter templates see their variables (`{{ cookiecutter.x }}`).
+const NAMESPACE: &str = "cookiecutter";
+
+/// Private keys that configure Cookiecutter itself rather than define a variable.
+const UNSUPPORTED_KEYS: &[&str] = &["_extensions", "_jinja2_env_vars", "_new_lines"];
+
+/// Templates described by a `cookiecutter.json`.
+pub struct CookiecutterAdapter;
+
+impl TemplateAdapter for CookiecutterAdapter {
+    fn name(&self) -> &str {
+        "cookiecutter"
+    }
+
+    fn detect(&self, template_dir: &Path) -> bool {
+        template_dir.join(CONFIG_FILE).is_file()
+    }
+
+    fn resolve(&self, template_dir: &Path) -> Result<ResolvedTemplate> {
+        resolve(template_dir)
+    }
+}
+
+/// Resolve a Cookiecutter template: variables come from `cookiecutter.json`,
+/// files from the top-level `{{cookiecutter.<name>}}/` directory.
+///
+/// Every file is rendered (Cookiecutter has no template suffix), and
+/// `cookiecutter.` references in defaults are rewritten to plain names so
+/// variables can depend on each other like in a native template.
+pub fn resolve(template_dir: &Path) -> Result<ResolvedTemplate> {
+    let config_path = template_dir.join(CONFIG_FILE);
+    let invalid = |reason: String| DicecutError::ForeignTemplate {
+        format: "Cookiecutter",
+        path: config_path.clone(),
+        reason,
+    };
+
+    let content = std::fs::read_to_string(&config_path).map_err(|e| DicecutError::Io {
+        context: format!("reading {}", config_path.display()),
+        source: e,
+    })?;
+    let mut entries: IndexMap<String, Value> =
+        serde_json::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+
+    let mut warnings = Vec::new();
+    let prompts = match entries.shift_remove("__prompts__") {
+        Some(Value::Object(prompts)) => prompts,
+        _ => serde_json::Map::new(),
+    };
+
+    let mut config = TemplateConfig {
+        template: TemplateMetadata {
+            name: template_name(template_dir, "cookiecutter"),
+            templates_suffix: Some(String::new()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    for (key, value) in entries {
+        if key == "_copy_without_render" {
+            match value {
+                Value::Array(patterns) => config.files.copy_without_render.extend(
+                    patterns
+                        .into_iter()
+                        .filter_map(|p| p.as_str().map(String::from)),
+                ),
+                _ => return Err(invalid("'_copy_without_render' must be a list".into())),
+            }
+            continue;
+        }
+        if UNSUPPORTED_KEYS.contains(&key.as_str()) {
+            warnings.push(format!(
+                "Cookiecutter option '{key}' is not supported and was ignored"
+            ));
+            continue;
+        }
+
+        let variable = if key.starts_with('_') {
+            private_variable(&key, value)
+        } else {
+            public_variable(value, prompts.get(&key))
+        };
+        match variable {
+            Some(var) => {
+                config.variables.insert(key, var);
+            }
+            None => warnings.push(format!(
+                "Cookiecutter variable '{key}' has an unsupported value and was ignored"
+            )),
+        }
+    }
+    config.validate()?;
+
+    if template_dir.join("hooks").is_dir() {
+        warnings.push(
+            "Cookiecutter hooks in hooks/ are not run; move them to [hooks] in a diecut.toml"
+                .into(),
+        );
+    }
+
+    Ok(ResolvedTemplate {
+        config,
+        content_dirs: vec![project_dir(template_dir).map_err(invalid)?],
+        partials_dirs: Vec::new(),
+        warnings,
+        namespace: Some(NAMESPACE.to_string()),
+        globals: Default::default(),
+        format: CookiecutterAdapter.name().to_string(),
+        skip_empty_paths: false,
+    })
+}
+
+/// A prompted variable. Lists become selects defaulting to their first
+/// entry; `__prompts__` supplies the prompt and, for lists, choice labels.
+fn public_variable(value: Value, prompt: Option<&Value>) -> Option<VariableConfig> {
+    let (prompt, labels) = match prompt {
+        Some(Value::String(text)) => (Some(text.clone()), None),
+        Some(Value::Object(map)) => (
+            map.get("__prompt__")
+                .and_then(Value::as_str)
+                .map(String::from),
+            Some(map),
+        ),
+        _ => (None, None),
+    };
+
+    let mut var = match value {
+        Value::Array(items) => {
+            let choices: Vec<Choice> = items
+                .iter()
+                .map(|item| {
+                    let value = scalar_to_string(item)?;
+                    let label = labels
+                        .and_then(|labels| labels.get(&value))
+                        .and_then(Value::as_str)
+                        .map(String::from);
+                    Some(Choice {
+                        label,
+                        ..Choice::from(value)
+                    })
+                })
+                .collect::<Option<_>>()?;
+            VariableConfig {
+                var_type: VariableType::Select,
+                default: Some(toml::Value::String(choices.first()?.value.clone())),
+                choices: Some(choices),
+                ..Default::default()
+            }
+        }
+        Value::Null => VariableConfig::default(),
+        scalar => {
+            let (var_type, default) = literal(&scalar)?;
+            VariableConfig {
+                var_type,
+                default: Some(match default {
+                    toml::Value::String(s) => toml::Value::String(strip_namespace(&s)),
+                    other => other,
+                }),
+                ..Default::default()
+            }
+        }
+    };
+    var.prompt = prompt;
+    Some(var)
+}
+
+/// A `_` or `__` key, which Cookiecutter never prompts for. `__` values are
+/// rendered, `_` values are taken literally.
+fn private_variable(key: &str, value: Value) -> Option<VariableConfig> {
+    let (var_type, literal) = literal(&value)?;
+    let computed = match literal {
+        toml::Value::String(s) if key.starts_with("__") => strip_namespace(&s),
+        toml::Value::String(s) if s.contains("{{") || s.contains("{%") => {
+            format!("{{% raw %}}{s}{{% endraw %}}")
+        }
+        toml::Value::String(s) => s,
+        other => other.to_string(),
+    };
+    Some(VariableConfig {
+        var_type,
+        computed: Some(computed),
+        ..Default::default()
+    })
+}
+
+/// The variable type and TOML value of a JSON scalar, or `None` for lists,
+/// objects, and null.
+fn literal(value: &Value) -> Option<(VariableType, toml::Value)> {
+    match value {
+        Value::String(s) => Some((VariableType::String, toml::Value::String(s.clone()))),
+        Value::Bool(b) => Some((VariableType::Bool, toml::Value::Boolean(*b))),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => Some((VariableType::Int, toml::Value::Integer(i))),
+            None => Some((VariableType::Float, toml::Value::Float(n.as_f64()?))),
+        },
+        _ => None,
+    }
+}
+
+/// Rewrite `cookiecutter.x` to `x`, so expressions evaluated before the
+/// namespace exists (defaults and computed values) still resolve.
+fn strip_namespace(template: &str) -> String {
+    let re = regex_lite::Regex::new(r"\bcookiecutter\.").expect("valid regex");
+    re.replace_all(template, "").into_owned()
+}
+
+/// The top-level directory whose name is a `cookiecutter.` expression.
+fn project_dir(template_dir: &Path) -> std::result::Result<PathBuf, String> {
+    let entries = std::fs::read_dir(template_dir).map_err(|e| e.to_string())?;
+    let mut candidates: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            name.contains("{{") && name.contains(NAMESPACE)
+        })
+        .collect();
+    candidates.sort();
+    candidates.into_iter().next().ok_or_else(|| {
+        "no top-level directory like '{{cookiecutter.project_slug}}' holds the project files"
+            .to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir_with;
+
+    #[test]
+    fn maps_cookiecutter_json_to_variables() {
+        let dir = temp_dir_with(&[
+            (
+                CONFIG_FILE,
+                r#"{
+                "project_name": "My Project",
+                "project_slug": "{{ cookiecutter.project_name | slugify }}",
+                "license": ["MIT", "BSD-3"],
+                "use_docker": false,
+                "workers": 4,
+                "_secret_prefix": "{{ not rendered }}",
+                "__module": "{{ cookiecutter.project_slug | replace(from='-', to='_') }}",
+                "_copy_without_render": ["*.png"],
+                "__prompts__": {
+                    "project_name": "What is the project called?",
+                    "license": {"__prompt__": "License", "BSD-3": "BSD 3-Clause"}
+                }
+            }"#,
+            ),
+            ("{{cookiecutter.project_slug}}/README.md", ""),
+        ]);
+
+        let resolved = resolve(dir.path()).unwrap();
+        let config = &resolved.config;
+        let names: Vec<&str> = config.variables.keys().map(String::as_str).collect();
+        assert_eq!(
+            names,
+            vec![
+                "project_name",
+                "project_slug",
+                "license",
+                "use_docker",
+                "workers",
+                "_secret_prefix",
+                "__module"
+            ]
+        );
+
+        let vars = &config.variables;
+        assert_eq!(
+            vars["project_name"].prompt.as_deref(),
+            Some("What is the project called?")
+        );
+        assert_eq!(
+            vars["project_slug"].templated_default(),
+            Some("{{ project_name | slugify }}")
+        );
+        assert_eq!(vars["license"].var_type, VariableType::Select);
+        assert_eq!(vars["license"].prompt.as_deref(), Some("License"));
+        let choices = vars["license"].choices.as_ref().unwrap();
+        assert_eq!(choices[1].label.as_deref(), Some("BSD 3-Clause"));
+        assert_eq!(
+            vars["license"].default,
+            Some(toml::Value::String("MIT".into()))
+        );
+        assert_eq!(vars["use_docker"].var_type, VariableType::Bool);
+        assert_eq!(vars["workers"].var_type, VariableType::Int);
+        assert_eq!(
+            vars["_secret_prefix"].computed.as_deref(),
+            Some("{% raw %}{{ not rendered }}{% endraw %}")
+        );
+        assert_eq!(
+            vars["__module"].computed.as_deref(),
+            Some("{{ project_slug | replace(from='-', to='_') }}")
+        );
+        assert_eq!(config.files.copy_without_render, vec!["*.png"]);
+        assert_eq!(config.template.templates_suffix.as_deref(), Some(""));
+        assert!(resolved.content_dirs[0].ends_with("{{cookiecutter.project_slug}}"));
+        assert_eq!(resolved.namespace.as_deref(), Some("cookiecutter"));
+    }
+
+    #[test]
+    fn unsupported_values_are_skipped_with_a_warning() {
+        let dir = temp_dir_with(&[
+            (
+                CONFIG_FILE,
+                r#"{"name": "x", "settings": {"a": 1}, "_extensions": []}"#,
+            ),
+            ("{{cookiecutter.project_slug}}/README.md", ""),
+        ]);
+
+        let resolved = resolve(dir.path()).unwrap();
+        assert!(resolved.config.variables.contains_key("name"));
+        assert!(!resolved.config.variables.contains_key("settings"));
+        assert_eq!(resolved.warnings.len(), 2, "{:?}", resolved.warnings);
+    }
+
+    #[test]
+    fn missing_project_directory_is_an_error() {
+        let dir = temp_dir_with(&[(CONFIG_FILE, r#"{"name": "x"}"#)]);
+
+        assert!(matches!(
+            resolve(dir.path()),
+            Err(DicecutError::ForeignTemplate { .. })
+        ));
+    }
+}
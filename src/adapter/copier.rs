@@ -0,0 +1,585 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use serde::Deserialize;
+use serde_json::Value;
+
+use crate::adapter::{scalar_to_string, template_name, ResolvedTemplate, TemplateAdapter};
+use crate::config::schema::{AnswersConfig, TemplateConfig, TemplateMetadata};
+use crate::config::variable::{Choice, VariableConfig, VariableType};
+use crate::error::{DicecutError, Result};
+
+pub const CONFIG_FILES: &[&str] = &["copier.yml", "copier.yaml"];
+
+/// Copier's suffix for rendered files.
+const DEFAULT_SUFFIX: &str = ".jinja";
+
+/// Copier's `_exclude` when a template doesn't set one.
+const DEFAULT_EXCLUDE: &[&str] = &[
+    "copier.yaml",
+    "copier.yml",
+    "~*",
+    "*.py[co]",
+    "__pycache__",
+    ".git",
+    ".DS_Store",
+    ".svn",
+];
+
+/// A question in long form. Keys diecut has no use for (`placeholder`,
+/// `qmark`, `multiline`) are accepted and ignored.
+#[derive(Debug, Default, Deserialize)]
+struct Question {
+    #[serde(rename = "type")]
+    kind: Option<String>,
+    help: Option<String>,
+    default: Option<Value>,
+    choices: Option<Choices>,
+    #[serde(default)]
+    multiselect: bool,
+    when: Option<Value>,
+    validator: Option<String>,
+    #[serde(default)]
+    secret: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Choices {
+    /// `[value, ...]` or `[[label, value], ...]`.
+    List(Vec<Value>),
+    /// `{label: value}` or `{label: {value: ...}}`.
+    Map(IndexMap<String, Value>),
+}
+
+/// Keys a question in long form may have; any other mapping is a shorthand default.
+const QUESTION_KEYS: &[&str] = &[
+    "type",
+    "help",
+    "default",
+    "choices",
+    "multiselect",
+    "when",
+    "validator",
+    "secret",
+    "placeholder",
+    "qmark",
+    "multiline",
+];
+
+/// Templates described by a `copier.yml` or `copier.yaml`.
+pub struct CopierAdapter;
+
+impl TemplateAdapter for CopierAdapter {
+    fn name(&self) -> &str {
+        "copier"
+    }
+
+    fn detect(&self, template_dir: &Path) -> bool {
+        config_file(template_dir).is_some()
+    }
+
+    fn resolve(&self, template_dir: &Path) -> Result<ResolvedTemplate> {
+        resolve(template_dir)
+    }
+}
+
+pub fn config_file(template_dir: &Path) -> Option<PathBuf> {
+    CONFIG_FILES
+        .iter()
+        .map(|name| template_dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// Resolve a Copier template from `copier.yml` (or `copier.yaml`).
+///
+/// Questions become variables and `_subdirectory`, `_exclude`,
+/// `_templates_suffix`, `_answers_file`, and `_secret_questions` are honored.
+/// Other settings, such as `_tasks` and `_migrations`, are reported as warnings.
+pub fn resolve(template_dir: &Path) -> Result<ResolvedTemplate> {
+    let config_path = config_file(template_dir).unwrap_or_else(|| template_dir.join("copier.yml"));
+    let invalid = |reason: String| DicecutError::ForeignTemplate {
+        format: "Copier",
+        path: config_path.clone(),
+        reason,
+    };
+
+    let content = std::fs::read_to_string(&config_path).map_err(|e| DicecutError::Io {
+        context: format!("reading {}", config_path.display()),
+        source: e,
+    })?;
+    // Kept as YAML values so mappings (choices) stay in file order.
+    let entries: IndexMap<String, serde_yaml_ng::Value> = if content.trim().is_empty() {
+        IndexMap::new()
+    } else {
+        serde_yaml_ng::from_str(&content).map_err(|e| invalid(e.to_string()))?
+    };
+
+    let mut warnings = Vec::new();
+    let mut config = TemplateConfig {
+        template: TemplateMetadata {
+            name: template_name(template_dir, "copier"),
+            templates_suffix: Some(DEFAULT_SUFFIX.to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let mut subdirectory = None;
+    let mut exclude = None;
+    let mut secret_questions = Vec::new();
+
+    for (key, value) in entries {
+        if !key.starts_with('_') {
+            let variable = question(&key, value, &mut warnings).map_err(&invalid)?;
+            if let Some(var) = variable {
+                config.variables.insert(key, var);
+            }
+            continue;
+        }
+        let value: Value =
+            serde_yaml_ng::from_value(value).map_err(|e| invalid(format!("'{key}': {e}")))?;
+        let setting = |value: Value| match value {
+            Value::String(s) => Ok(s),
+            _ => Err(invalid(format!("'{key}' must be a string"))),
+        };
+        match key.as_str() {
+            "_subdirectory" => subdirectory = Some(setting(value)?),
+            "_templates_suffix" => config.template.templates_suffix = Some(setting(value)?),
+            "_answers_file" => {
+                // diecut always writes its answers as TOML, so keep the default
+                // file rather than put TOML under a YAML name.
+                let file = setting(value)?;
+                if file.ends_with(".toml") {
+                    config.answers = AnswersConfig { file };
+                } else {
+                    warnings.push(format!(
+                        "'_answers_file' '{file}' is not a TOML file; answers are saved to {} instead",
+                        config.answers.file
+                    ));
+                }
+            }
+            "_exclude" => exclude = Some(string_list(&key, value).map_err(&invalid)?),
+            "_secret_questions" => {
+                secret_questions = string_list(&key, value).map_err(&invalid)?;
+            }
+            _ => warnings.push(format!(
+                "Copier option '{key}' is not supported and was ignored"
+            )),
+        }
+    }
+
+    for name in secret_questions {
+        match config.variables.get_mut(&name) {
+            Some(var) => var.secret = true,
+            None => warnings.push(format!(
+                "'_secret_questions' names '{name}', which is not a question"
+            )),
+        }
+    }
+
+    let mut patterns: Vec<String> = match exclude {
+        Some(patterns) => patterns,
+        None => DEFAULT_EXCLUDE.iter().map(|p| p.to_string()).collect(),
+    };
+    if subdirectory.is_none() {
+        patterns.extend(CONFIG_FILES.iter().map(|p| p.to_string()));
+    }
+    patterns.push(".git".into());
+    for pattern in patterns {
+        if pattern.starts_with('!') {
+            warnings.push(format!(
+                "Negated '_exclude' pattern '{pattern}' is not supported and was ignored"
+            ));
+            continue;
+        }
+        for glob in exclude_globs(&pattern) {
+            if !config.files.exclude.contains(&glob) {
+                config.files.exclude.push(glob);
+            }
+        }
+    }
+    config.validate()?;
+
+    let content_dir = match &subdirectory {
+        Some(sub) => template_dir.join(sub),
+        None => template_dir.to_path_buf(),
+    };
+    let copier_conf = serde_json::json!({
+        "src_path": template_dir
+            .canonicalize()
+            .unwrap_or_else(|_| template_dir.to_path_buf())
+            .display()
+            .to_string(),
+        "answers_file": config.answers.file,
+        "sep": std::path::MAIN_SEPARATOR.to_string(),
+    });
+
+    Ok(ResolvedTemplate {
+        config,
+        content_dirs: vec![content_dir],
+        partials_dirs: Vec::new(),
+        warnings,
+        namespace: None,
+        globals: BTreeMap::from([("_copier_conf".to_string(), copier_conf)]),
+        format: CopierAdapter.name().to_string(),
+        skip_empty_paths: true,
+    })
+}
+
+/// Translate one question. `None` means it was skipped with a warning.
+fn question(
+    name: &str,
+    value: serde_yaml_ng::Value,
+    warnings: &mut Vec<String>,
+) -> std::result::Result<Option<VariableConfig>, String> {
+    let invalid = |e: serde_yaml_ng::Error| format!("question '{name}': {e}");
+    let is_long_form = value.as_mapping().is_some_and(|map| {
+        map.keys()
+            .all(|k| k.as_str().is_some_and(|k| QUESTION_KEYS.contains(&k)))
+    });
+    let question = if is_long_form {
+        serde_yaml_ng::from_value::<Question>(value).map_err(invalid)?
+    } else {
+        Question {
+            default: Some(serde_yaml_ng::from_value(value).map_err(invalid)?),
+            ..Default::default()
+        }
+    };
+
+    let mut var = VariableConfig {
+        prompt: question.help,
+        validator: question.validator,
+        secret: question.secret,
+        ..Default::default()
+    };
+
+    if let Some(choices) = question.choices {
+        var.var_type = if question.multiselect {
+            VariableType::Multiselect
+        } else {
+            VariableType::Select
+        };
+        var.choices = Some(choice_list(name, choices)?);
+        var.default = match question.default {
+            Some(Value::Array(items)) => Some(toml::Value::Array(
+                items
+                    .iter()
+                    .filter_map(scalar_to_string)
+                    .map(toml::Value::String)
+                    .collect(),
+            )),
+            Some(value) => scalar_to_string(&value).map(toml::Value::String),
+            None => None,
+        };
+    } else {
+        var.var_type = match question.kind.as_deref() {
+            Some("str") => VariableType::String,
+            Some("bool") => VariableType::Bool,
+            Some("int") => VariableType::Int,
+            Some("float") => VariableType::Float,
+            Some(kind @ ("json" | "yaml")) => {
+                warnings.push(format!(
+                    "Question '{name}' has type '{kind}', which is read as a string"
+                ));
+                VariableType::String
+            }
+            Some(kind) => return Err(format!("question '{name}' has unknown type '{kind}'")),
+            None => inferred_type(question.default.as_ref()),
+        };
+        var.default = match question.default {
+            None | Some(Value::Null) => None,
+            Some(Value::Array(items)) if var.var_type == VariableType::List => {
+                Some(toml::Value::Array(
+                    items
+                        .iter()
+                        .filter_map(scalar_to_string)
+                        .map(toml::Value::String)
+                        .collect(),
+                ))
+            }
+            Some(value) => match toml_scalar(&value) {
+                Some(default) => Some(default),
+                None => {
+                    warnings.push(format!(
+                        "Question '{name}' has a default diecut can't represent; it was ignored"
+                    ));
+                    return Ok(None);
+                }
+            },
+        };
+    }
+
+    match question.when {
+        None | Some(Value::Bool(true)) => {}
+        // Never asked: Copier uses the default as a hidden value.
+        Some(Value::Bool(false)) => {
+            let Some(default) = var.default.take() else {
+                warnings.push(format!(
+                    "Question '{name}' is never asked and has no default; it was ignored"
+                ));
+                return Ok(None);
+            };
+            var.prompt = None;
+            var.computed = Some(match default {
+                toml::Value::String(s) => s,
+                other => other.to_string(),
+            });
+        }
+        Some(Value::String(expr)) => var.when = Some(bare_expression(&expr)),
+        Some(other) => {
+            return Err(format!(
+                "question '{name}': 'when' must be a bool or string, not {other}"
+            ))
+        }
+    }
+
+    Ok(Some(var))
+}
+
+fn choice_list(name: &str, choices: Choices) -> std::result::Result<Vec<Choice>, String> {
+    let invalid = || format!("question '{name}' has a choice diecut can't represent");
+    let labeled = |label: &str, value: &Value| {
+        let value = match value {
+            Value::Object(map) => map.get("value").and_then(scalar_to_string),
+            other => scalar_to_string(other),
+        }?;
+        Some(Choice {
+            label: (label != value).then(|| label.to_string()),
+            ..Choice::from(value)
+        })
+    };
+    match choices {
+        Choices::List(items) => items
+            .iter()
+            .map(|item| match item {
+                Value::Array(pair) if pair.len() == 2 => {
+                    labeled(&scalar_to_string(&pair[0])?, &pair[1])
+                }
+                other => scalar_to_string(other).map(Choice::from),
+            })
+            .collect::<Option<_>>()
+            .ok_or_else(invalid),
+        Choices::Map(map) => map
+            .iter()
+            .map(|(label, value)| labeled(label, value))
+            .collect::<Option<_>>()
+            .ok_or_else(invalid),
+    }
+}
+
+/// Copier writes `when` as a template (`"{{ use_ci }}"`); diecut wants the expression.
+fn bare_expression(when: &str) -> String {
+    let trimmed = when.trim();
+    match trimmed
+        .strip_prefix("{{")
+        .and_then(|rest| rest.strip_suffix("}}"))
+    {
+        Some(inner) if !inner.contains("{{") && !inner.contains("}}") => inner.trim().to_string(),
+        _ => trimmed.to_string(),
+    }
+}
+
+fn inferred_type(default: Option<&Value>) -> VariableType {
+    match default {
+        Some(Value::Bool(_)) => VariableType::Bool,
+        Some(Value::Number(n)) if n.is_i64() => VariableType::Int,
+        Some(Value::Number(_)) => VariableType::Float,
+        Some(Value::Array(items)) if items.iter().all(|i| scalar_to_string(i).is_some()) => {
+            VariableType::List
+        }
+        _ => VariableType::String,
+    }
+}
+
+fn toml_scalar(value: &Value) -> Option<toml::Value> {
+    match value {
+        Value::String(s) => Some(toml::Value::String(s.clone())),
+        Value::Bool(b) => Some(toml::Value::Boolean(*b)),
+        Value::Number(n) => match n.as_i64() {
+            Some(i) => Some(toml::Value::Integer(i)),
+            None => n.as_f64().map(toml::Value::Float),
+        },
+        _ => None,
+    }
+}
+
+fn string_list(key: &str, value: Value) -> std::result::Result<Vec<String>, String> {
+    match value {
+        Value::Array(items) => items
+            .into_iter()
+            .map(|item| match item {
+                Value::String(s) => Ok(s),
+                other => Err(format!("'{key}' entries must be strings, not {other}")),
+            })
+            .collect(),
+        _ => Err(format!("'{key}' must be a list")),
+    }
+}
+
+/// Globs for a gitignore-style `_exclude` pattern. A pattern without a `/`
+/// matches at any depth; anything it matches excludes the whole subtree.
+fn exclude_globs(pattern: &str) -> Vec<String> {
+    let pattern = pattern.trim_end_matches('/');
+    match pattern.strip_prefix('/') {
+        Some(anchored) => vec![anchored.to_string(), format!("{anchored}/**")],
+        None if pattern.contains('/') => vec![pattern.to_string(), format!("{pattern}/**")],
+        None => vec![format!("**/{pattern}"), format!("**/{pattern}/**")],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::temp_dir_with;
+    use rstest::rstest;
+    use std::fs;
+
+    fn resolve_yaml(yaml: &str) -> ResolvedTemplate {
+        let dir = temp_dir_with(&[("copier.yml", yaml)]);
+        resolve(dir.path()).unwrap()
+    }
+
+    #[test]
+    fn maps_questions_to_variables() {
+        let resolved = resolve_yaml(
+            r#"
+project_name:
+  type: str
+  help: What is the project called?
+  validator: "{% if not project_name %}Required{% endif %}"
+package: "{{ project_name | slugify }}"
+license:
+  type: str
+  choices:
+    MIT License: MIT
+    Apache 2.0: Apache-2.0
+  default: MIT
+features:
+  choices: [api, cli]
+  multiselect: true
+  default: [cli]
+use_ci: true
+ci:
+  type: str
+  choices: [github, gitlab]
+  when: "{{ use_ci }}"
+token:
+  type: str
+  secret: true
+  default: ""
+internal:
+  type: int
+  default: 3
+  when: false
+"#,
+        );
+        let vars = &resolved.config.variables;
+
+        let project_name = &vars["project_name"];
+        assert_eq!(
+            project_name.prompt.as_deref(),
+            Some("What is the project called?")
+        );
+        assert!(project_name.validator.is_some());
+        assert_eq!(
+            vars["package"].templated_default(),
+            Some("{{ project_name | slugify }}")
+        );
+
+        let license = &vars["license"];
+        assert_eq!(license.var_type, VariableType::Select);
+        let choices = license.choices.as_ref().unwrap();
+        assert_eq!(choices[0].value, "MIT");
+        assert_eq!(choices[0].label.as_deref(), Some("MIT License"));
+
+        assert_eq!(vars["features"].var_type, VariableType::Multiselect);
+        assert_eq!(vars["use_ci"].var_type, VariableType::Bool);
+        assert_eq!(vars["ci"].when.as_deref(), Some("use_ci"));
+        assert!(vars["token"].secret);
+        assert_eq!(vars["internal"].computed.as_deref(), Some("3"));
+        assert_eq!(vars["internal"].var_type, VariableType::Int);
+    }
+
+    #[test]
+    fn honors_template_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("copier.yaml"),
+            r#"
+_subdirectory: template
+_templates_suffix: .tmpl
+_answers_file: .answers.toml
+_exclude: ["*.bak", "/docs/"]
+_secret_questions: [token]
+_tasks: ["git init"]
+_migrations: []
+token: ""
+"#,
+        )
+        .unwrap();
+        let resolved = resolve(dir.path()).unwrap();
+        let config = &resolved.config;
+
+        assert!(resolved.content_dirs[0].ends_with("template"));
+        assert_eq!(config.template.templates_suffix.as_deref(), Some(".tmpl"));
+        assert_eq!(config.answers.file, ".answers.toml");
+        assert_eq!(
+            config.files.exclude,
+            vec![
+                "**/*.bak",
+                "**/*.bak/**",
+                "docs",
+                "docs/**",
+                "**/.git",
+                "**/.git/**"
+            ]
+        );
+        assert!(config.variables["token"].secret);
+        assert_eq!(
+            resolved.warnings,
+            vec![
+                "Copier option '_tasks' is not supported and was ignored",
+                "Copier option '_migrations' is not supported and was ignored",
+            ]
+        );
+        assert_eq!(
+            resolved.globals["_copier_conf"]["answers_file"],
+            ".answers.toml"
+        );
+    }
+
+    #[test]
+    fn keeps_toml_answers_file_for_yaml_name() {
+        let resolved = resolve_yaml("_answers_file: .copier-answers.yml\n");
+
+        assert_eq!(resolved.config.answers.file, ".diecut-answers.toml");
+        assert_eq!(
+            resolved.warnings,
+            vec![
+                "'_answers_file' '.copier-answers.yml' is not a TOML file; answers are saved to .diecut-answers.toml instead"
+            ]
+        );
+        assert_eq!(
+            resolved.globals["_copier_conf"]["answers_file"],
+            ".diecut-answers.toml"
+        );
+    }
+
+    #[rstest]
+    #[case("{{ use_ci }}", "use_ci")]
+    #[case("{{ ci == 'github' }}", "ci == 'github'")]
+    #[case("use_ci and docs", "use_ci and docs")]
+    fn when_templates_become_expressions(#[case] when: &str, #[case] expected: &str) {
+        assert_eq!(bare_expression(when), expected);
+    }
+
+    #[test]
+    fn unknown_type_is_an_error() {
+        let dir = temp_dir_with(&[("copier.yml", "x:\n  type: path\n")]);
+        assert!(matches!(
+            resolve(dir.path()),
+            Err(DicecutError::ForeignTemplate { .. })
+        ));
+    }
+}
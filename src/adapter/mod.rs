@@ -1,16 +1,184 @@
+pub mod cookiecutter;
+pub mod copier;
 pub mod native;
+pub mod registry;
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
-use crate::config::schema::TemplateConfig;
-use crate::error::Result;
+use indexmap::IndexMap;
+use tera::{Context, Value};
+use walkdir::WalkDir;
+
+use crate::config::schema::{TemplateConfig, DEFAULT_TEMPLATES_SUFFIX};
+use crate::error::{DicecutError, Result};
+use crate::render::build_context;
+
+pub use registry::{register_adapter, registry, AdapterRegistry, TemplateAdapter};
 
 pub struct ResolvedTemplate {
     pub config: TemplateConfig,
-    pub content_dir: PathBuf,
+    /// Content roots, base templates first. A file in a later root replaces
+    /// the file at the same path in an earlier one.
+    pub content_dirs: Vec<PathBuf>,
+    /// `partials/` directories, in the same order as `content_dirs`.
+    pub partials_dirs: Vec<PathBuf>,
     pub warnings: Vec<String>,
+    /// If set, variables are also exposed under this name when rendering
+    /// files, e.g. `cookiecutter` for `{{ cookiecutter.project_slug }}`.
+    pub namespace: Option<String>,
+    /// Values added to the render context besides the variables, such as
+    /// Copier's `_copier_conf`.
+    pub globals: BTreeMap<String, Value>,
+    /// Name of the adapter that read the template, e.g. `native` or `copier`.
+    pub format: String,
+    /// Skip a file when any component of its rendered path is empty, as
+    /// Copier does for names like `{% if x %}name{% endif %}`.
+    pub skip_empty_paths: bool,
+}
+
+/// A file in the merged content tree of a template.
+pub struct ContentFile {
+    /// Path relative to the content root it came from.
+    pub rel_path: PathBuf,
+    /// Location on disk.
+    pub src_path: PathBuf,
+}
+
+impl ResolvedTemplate {
+    /// The Tera context files and paths are rendered with.
+    pub fn render_context(&self, variables: &BTreeMap<String, Value>) -> Context {
+        let mut context = build_context(variables);
+        if let Some(namespace) = &self.namespace {
+            context.insert(namespace, variables);
+        }
+        for (name, value) in &self.globals {
+            context.insert(name, value);
+        }
+        context
+    }
+
+    /// Overlay all content roots into a single list of files.
+    ///
+    /// Paths are compared with the template suffix stripped, so a child's
+    /// plain `README.md` replaces a base's `README.md.die`. Roots that don't
+    /// exist are skipped; it is an error if none of them exist.
+    pub fn content_files(&self) -> Result<Vec<ContentFile>> {
+        let existing: Vec<&PathBuf> = self.content_dirs.iter().filter(|d| d.exists()).collect();
+        if existing.is_empty() {
+            return Err(DicecutError::TemplateDirectoryMissing {
+                path: self.content_dirs.last().cloned().unwrap_or_default(),
+            });
+        }
+
+        let suffix = self
+            .config
+            .template
+            .templates_suffix
+            .as_deref()
+            .unwrap_or(DEFAULT_TEMPLATES_SUFFIX);
+
+        let mut files: IndexMap<String, ContentFile> = IndexMap::new();
+        for content_dir in existing {
+            for entry in WalkDir::new(content_dir)
+                .min_depth(1)
+                .sort_by_file_name()
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| !e.file_type().is_dir())
+            {
+                let rel_path = entry
+                    .path()
+                    .strip_prefix(content_dir)
+                    .expect("entry must be under content_dir")
+                    .to_path_buf();
+                let rel_str = rel_path.to_string_lossy();
+                let key = if suffix.is_empty() {
+                    rel_str.to_string()
+                } else {
+                    rel_str.strip_suffix(suffix).unwrap_or(&rel_str).to_string()
+                };
+                files.insert(
+                    key,
+                    ContentFile {
+                        rel_path,
+                        src_path: entry.path().to_path_buf(),
+                    },
+                );
+            }
+        }
+        Ok(files.into_values().collect())
+    }
+
+    /// Files in the `partials/` directories, keyed by their Tera name (the
+    /// path relative to `partials/`, with `/` separators). A partial in a
+    /// later directory replaces one with the same name in an earlier one.
+    pub fn partial_files(&self) -> Vec<(String, PathBuf)> {
+        let mut partials: IndexMap<String, PathBuf> = IndexMap::new();
+        for dir in self.partials_dirs.iter().filter(|d| d.exists()) {
+            for entry in WalkDir::new(dir)
+                .min_depth(1)
+                .sort_by_file_name()
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| !e.file_type().is_dir())
+            {
+                let rel = entry
+                    .path()
+                    .strip_prefix(dir)
+                    .expect("entry must be under partials dir");
+                partials.insert(tera_name(rel), entry.path().to_path_buf());
+            }
+        }
+        partials.into_iter().collect()
+    }
 }
 
+/// A relative path as a Tera template name, with `/` separators on every platform.
+pub(crate) fn tera_name(rel_path: &std::path::Path) -> String {
+    rel_path
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// A foreign template's name: the name of its directory, or `fallback`.
+pub(crate) fn template_name(template_dir: &std::path::Path, fallback: &str) -> String {
+    template_dir
+        .canonicalize()
+        .unwrap_or_else(|_| template_dir.to_path_buf())
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| fallback.to_string())
+}
+
+/// A string, bool, or number from a foreign config as text.
+pub(crate) fn scalar_to_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Bool(_) | Value::Number(_) => Some(value.to_string()),
+        _ => None,
+    }
+}
+
+/// Resolve a template directory with the first registered adapter that recognizes it.
 pub fn resolve_template(template_dir: &std::path::Path) -> Result<ResolvedTemplate> {
-    native::resolve(template_dir)
+    registry().resolve(template_dir, None)
+}
+
+/// Resolve a template directory with the adapter named `format`, or by
+/// detection when `format` is `None`.
+pub fn resolve_template_as(
+    template_dir: &std::path::Path,
+    format: Option<&str>,
+) -> Result<ResolvedTemplate> {
+    registry().resolve(template_dir, format)
+}
+
+/// Name of the adapter that would read `template_dir`, if any recognizes it.
+pub fn detect_format(template_dir: &std::path::Path) -> Option<String> {
+    registry()
+        .detect(template_dir)
+        .map(|adapter| adapter.name().to_string())
 }
@@ -1,19 +1,52 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use walkdir::WalkDir;
 
-use crate::adapter::ResolvedTemplate;
+use crate::adapter::{ResolvedTemplate, TemplateAdapter};
 use crate::config::load_config;
-use crate::config::schema::{DEFAULT_TEMPLATES_SUFFIX, DEPRECATED_TERA_SUFFIX};
-use crate::error::Result;
+use crate::config::schema::{TemplateConfig, DEFAULT_TEMPLATES_SUFFIX, DEPRECATED_TERA_SUFFIX};
+use crate::config::{load_user_config, UserConfig};
+use crate::error::{DicecutError, Result};
+use crate::template::{
+    get_or_clone, resolve_git_protocol, resolve_source, ResolveOptions, TemplateSource,
+};
+
+/// How many `extends` hops are followed before giving up.
+const MAX_EXTENDS_DEPTH: usize = 16;
+
+/// Templates described by a `diecut.toml`.
+pub struct NativeAdapter;
+
+impl TemplateAdapter for NativeAdapter {
+    fn name(&self) -> &str {
+        "native"
+    }
+
+    fn detect(&self, template_dir: &Path) -> bool {
+        template_dir.join("diecut.toml").is_file()
+    }
+
+    fn resolve(&self, template_dir: &Path) -> Result<ResolvedTemplate> {
+        resolve(template_dir)
+    }
+}
 
 pub fn resolve(template_dir: &Path) -> Result<ResolvedTemplate> {
-    let mut config = load_config(template_dir)?;
-    let content_dir = template_dir.join("template");
     let mut warnings = Vec::new();
+    let mut chain = Vec::new();
+    let (mut config, layer_dirs) = load_layers(template_dir, &mut chain, &mut None)?;
+    let content_dirs: Vec<PathBuf> = layer_dirs.iter().map(|d| d.join("template")).collect();
+    let partials_dirs = layer_dirs.iter().map(|d| d.join("partials")).collect();
+
+    if let Some(message) = &config.template.deprecated {
+        warnings.push(format!("This template is deprecated: {message}"));
+    }
 
     if config.template.templates_suffix.is_none() {
-        if content_dir.exists() && has_files_with_suffix(&content_dir, DEPRECATED_TERA_SUFFIX) {
+        if content_dirs
+            .iter()
+            .any(|dir| dir.exists() && has_files_with_suffix(dir, DEPRECATED_TERA_SUFFIX))
+        {
             config.template.templates_suffix = Some(DEPRECATED_TERA_SUFFIX.to_string());
             warnings.push(format!(
                 "This template uses {DEPRECATED_TERA_SUFFIX} file extensions. \
@@ -28,11 +61,104 @@ pub fn resolve(template_dir: &Path) -> Result<ResolvedTemplate> {
 
     Ok(ResolvedTemplate {
         config,
-        content_dir,
+        content_dirs,
+        partials_dirs,
         warnings,
+        namespace: None,
+        globals: Default::default(),
+        format: NativeAdapter.name().to_string(),
+        skip_empty_paths: false,
     })
 }
 
+/// Load a template's config merged with everything it `extends`, and the
+/// directory of each layer (base first).
+///
+/// `chain` holds the templates already visited, to report cycles. The user
+/// config is loaded lazily, only if a base needs an abbreviation expanded.
+fn load_layers(
+    template_dir: &Path,
+    chain: &mut Vec<PathBuf>,
+    user_config: &mut Option<UserConfig>,
+) -> Result<(TemplateConfig, Vec<PathBuf>)> {
+    let canonical = template_dir
+        .canonicalize()
+        .unwrap_or_else(|_| template_dir.to_path_buf());
+    if chain.contains(&canonical) || chain.len() >= MAX_EXTENDS_DEPTH {
+        chain.push(canonical);
+        return Err(DicecutError::ExtendsCycle {
+            chain: chain
+                .iter()
+                .map(|p| p.display().to_string())
+                .collect::<Vec<_>>()
+                .join(" -> "),
+        });
+    }
+    chain.push(canonical);
+
+    let config = load_config(template_dir)?;
+
+    let Some(extends) = config.template.extends.clone() else {
+        return Ok((config, vec![template_dir.to_path_buf()]));
+    };
+
+    let base_dir = locate_base(template_dir, &extends, user_config)?;
+    let (base_config, mut layer_dirs) = load_layers(&base_dir, chain, user_config)?;
+    layer_dirs.push(template_dir.to_path_buf());
+    let merged = config.inherit(base_config);
+    merged.validate_each_rules()?;
+    Ok((merged, layer_dirs))
+}
+
+/// Find the directory of a base template named in `extends`.
+///
+/// Relative local paths are resolved against the extending template's directory.
+fn locate_base(
+    template_dir: &Path,
+    extends: &str,
+    user_config: &mut Option<UserConfig>,
+) -> Result<PathBuf> {
+    let local = template_dir.join(extends);
+    if local.exists() {
+        return Ok(local);
+    }
+
+    if user_config.is_none() {
+        *user_config = Some(load_user_config()?.unwrap_or_default());
+    }
+    let user_config = user_config.as_ref().expect("loaded above");
+
+    let source = resolve_source(
+        extends,
+        ResolveOptions {
+            git_ref: None,
+            protocol: resolve_git_protocol(None, user_config.protocol.as_deref())?,
+            user_abbreviations: Some(&user_config.abbreviations),
+        },
+    )?;
+
+    match source {
+        TemplateSource::Local(_) => Err(DicecutError::ConfigNotFound {
+            path: local.join("diecut.toml"),
+        }),
+        TemplateSource::Git {
+            url,
+            git_ref,
+            subpath,
+        } => {
+            let (path, _) = get_or_clone(&url, git_ref.as_deref())?;
+            let path = match subpath {
+                Some(sub) => path.join(sub),
+                None => path,
+            };
+            if !path.exists() {
+                return Err(DicecutError::TemplateDirectoryMissing { path });
+            }
+            Ok(path)
+        }
+    }
+}
+
 fn has_files_with_suffix(dir: &Path, suffix: &str) -> bool {
     WalkDir::new(dir)
         .into_iter()
@@ -0,0 +1,190 @@
+use std::path::Path;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::adapter::{cookiecutter, copier, native, ResolvedTemplate};
+use crate::error::{DicecutError, Result};
+
+/// A template format diecut can read.
+///
+/// Implement this to support an in-house format, then add it with
+/// [`register_adapter`] so `diecut::generate` and friends can use it.
+pub trait TemplateAdapter: Send + Sync {
+    /// Short name used by `--format` and shown by `diecut check` and `diecut list`.
+    fn name(&self) -> &str;
+
+    /// Whether `template_dir` looks like a template in this format.
+    fn detect(&self, template_dir: &Path) -> bool;
+
+    /// Load the template. The returned [`ResolvedTemplate::format`] should be [`Self::name`].
+    fn resolve(&self, template_dir: &Path) -> Result<ResolvedTemplate>;
+}
+
+/// Adapters in priority order: the first one that detects a directory resolves it.
+#[derive(Clone)]
+pub struct AdapterRegistry {
+    adapters: Vec<Arc<dyn TemplateAdapter>>,
+}
+
+impl Default for AdapterRegistry {
+    /// The built-in adapters: native, then Cookiecutter, then Copier.
+    fn default() -> Self {
+        Self {
+            adapters: vec![
+                Arc::new(native::NativeAdapter),
+                Arc::new(cookiecutter::CookiecutterAdapter),
+                Arc::new(copier::CopierAdapter),
+            ],
+        }
+    }
+}
+
+impl AdapterRegistry {
+    /// Add an adapter ahead of those already registered, replacing any with the same name.
+    pub fn register(&mut self, adapter: impl TemplateAdapter + 'static) {
+        self.adapters.retain(|a| a.name() != adapter.name());
+        self.adapters.insert(0, Arc::new(adapter));
+    }
+
+    /// Adapter names, highest priority first.
+    pub fn names(&self) -> Vec<&str> {
+        self.adapters.iter().map(|a| a.name()).collect()
+    }
+
+    /// The first adapter that recognizes `template_dir`.
+    pub fn detect(&self, template_dir: &Path) -> Option<&dyn TemplateAdapter> {
+        self.adapters
+            .iter()
+            .find(|a| a.detect(template_dir))
+            .map(|a| a.as_ref())
+    }
+
+    /// Resolve with the adapter named `format`, or the first that detects the directory.
+    ///
+    /// If none does, the native adapter tries anyway, so the error names the
+    /// missing `diecut.toml`.
+    pub fn resolve(&self, template_dir: &Path, format: Option<&str>) -> Result<ResolvedTemplate> {
+        let adapter = match format {
+            Some(format) => self
+                .adapters
+                .iter()
+                .find(|a| a.name() == format)
+                .map(|a| a.as_ref())
+                .ok_or_else(|| DicecutError::UnknownTemplateFormat {
+                    format: format.to_string(),
+                    known: self.names().join(", "),
+                })?,
+            None => match self.detect(template_dir) {
+                Some(adapter) => adapter,
+                None => return native::resolve(template_dir),
+            },
+        };
+        adapter.resolve(template_dir)
+    }
+}
+
+fn global() -> &'static RwLock<AdapterRegistry> {
+    static REGISTRY: OnceLock<RwLock<AdapterRegistry>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Register an adapter for every later template resolution in this process.
+///
+/// It is tried before the adapters already registered, including the built-ins.
+pub fn register_adapter(adapter: impl TemplateAdapter + 'static) {
+    global()
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .register(adapter);
+}
+
+/// A snapshot of the process-wide registry.
+pub fn registry() -> AdapterRegistry {
+    global().read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::schema::{TemplateConfig, TemplateMetadata};
+
+    struct MarkerAdapter;
+
+    impl TemplateAdapter for MarkerAdapter {
+        fn name(&self) -> &str {
+            "marker"
+        }
+
+        fn detect(&self, template_dir: &Path) -> bool {
+            template_dir.join("template.marker").exists()
+        }
+
+        fn resolve(&self, template_dir: &Path) -> Result<ResolvedTemplate> {
+            Ok(ResolvedTemplate {
+                config: TemplateConfig {
+                    template: TemplateMetadata {
+                        name: "marked".into(),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                },
+                content_dirs: vec![template_dir.join("files")],
+                partials_dirs: Vec::new(),
+                warnings: Vec::new(),
+                namespace: None,
+                globals: Default::default(),
+                format: self.name().to_string(),
+                skip_empty_paths: false,
+            })
+        }
+    }
+
+    #[test]
+    fn detects_builtin_formats_in_priority_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = AdapterRegistry::default();
+        assert_eq!(registry.names(), vec!["native", "cookiecutter", "copier"]);
+        assert!(registry.detect(dir.path()).is_none());
+
+        std::fs::write(dir.path().join("copier.yml"), "").unwrap();
+        assert_eq!(registry.detect(dir.path()).unwrap().name(), "copier");
+
+        std::fs::write(dir.path().join("diecut.toml"), "").unwrap();
+        assert_eq!(registry.detect(dir.path()).unwrap().name(), "native");
+    }
+
+    #[test]
+    fn registered_adapters_take_priority() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("template.marker"), "").unwrap();
+        std::fs::write(dir.path().join("cookiecutter.json"), "{}").unwrap();
+
+        let mut registry = AdapterRegistry::default();
+        registry.register(MarkerAdapter);
+        assert_eq!(
+            registry.names(),
+            vec!["marker", "native", "cookiecutter", "copier"]
+        );
+
+        let resolved = registry.resolve(dir.path(), None).unwrap();
+        assert_eq!(resolved.format, "marker");
+        assert_eq!(resolved.config.template.name, "marked");
+    }
+
+    #[test]
+    fn format_override_skips_detection() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("cookiecutter.json"), "{}").unwrap();
+        let registry = AdapterRegistry::default();
+
+        match registry.resolve(dir.path(), Some("native")) {
+            Err(DicecutError::ConfigNotFound { .. }) => {}
+            other => panic!("expected the native adapter to run, got {:?}", other.err()),
+        }
+        match registry.resolve(dir.path(), Some("jinja")) {
+            Err(DicecutError::UnknownTemplateFormat { known, .. }) => {
+                assert_eq!(known, "native, cookiecutter, copier")
+            }
+            other => panic!("expected an unknown format error, got {:?}", other.err()),
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+use tera::Value;
+
+use crate::error::{DicecutError, Result};
+
+/// Prefix of environment variables that answer a variable: `DIECUT_VAR_<name>`.
+pub const VAR_ENV_PREFIX: &str = "DIECUT_VAR_";
+
+/// Load variable values from a `--data-file`.
+///
+/// The format follows the extension: `.json`, `.toml`, or `.yaml`/`.yml`. The
+/// top level must be a table whose keys are variable names; values keep their
+/// native types, so lists and nested tables arrive as-is.
+pub fn load_data_file(path: &Path) -> Result<HashMap<String, Value>> {
+    let content = std::fs::read_to_string(path).map_err(|e| DicecutError::Io {
+        context: format!("reading data file {}", path.display()),
+        source: e,
+    })?;
+    let parse_error = |reason: String| DicecutError::DataFileParse {
+        path: path.to_path_buf(),
+        reason,
+    };
+
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(str::to_ascii_lowercase);
+    let value: Value = match extension.as_deref() {
+        Some("json") => serde_json::from_str(&content).map_err(|e| parse_error(e.to_string()))?,
+        Some("toml") => toml::from_str::<toml::Table>(&content)
+            .map(|table| crate::prompt::engine::toml_to_tera_value(&toml::Value::Table(table)))
+            .map_err(|e| parse_error(e.to_string()))?,
+        Some("yaml" | "yml") => {
+            serde_yaml_ng::from_str(&content).map_err(|e| parse_error(e.to_string()))?
+        }
+        _ => {
+            return Err(parse_error(
+                "unknown format; use a .json, .toml, .yaml or .yml file".into(),
+            ))
+        }
+    };
+
+    match value {
+        Value::Object(map) => Ok(map.into_iter().collect()),
+        Value::Null => Ok(HashMap::new()),
+        _ => Err(parse_error(
+            "expected a table of variable names to values".into(),
+        )),
+    }
+}
+
+/// Collect `DIECUT_VAR_<name>` environment variables, keyed by `<name>`.
+pub fn data_from_env() -> HashMap<String, String> {
+    std::env::vars()
+        .filter_map(|(key, value)| {
+            let name = key.strip_prefix(VAR_ENV_PREFIX)?;
+            (!name.is_empty()).then(|| (name.to_string(), value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    #[rstest]
+    #[case(
+        "answers.json",
+        r#"{"name": "svc", "port": 8080, "features": ["api", "cli"], "owner": {"team": "core"}}"#
+    )]
+    #[case(
+        "answers.toml",
+        "name = \"svc\"\nport = 8080\nfeatures = [\"api\", \"cli\"]\n\n[owner]\nteam = \"core\"\n"
+    )]
+    #[case(
+        "answers.yaml",
+        "name: svc\nport: 8080\nfeatures:\n  - api\n  - cli\nowner:\n  team: core\n"
+    )]
+    fn load_data_file_formats(#[case] file_name: &str, #[case] content: &str) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(file_name);
+        std::fs::write(&path, content).unwrap();
+
+        let data = load_data_file(&path).unwrap();
+        assert_eq!(data["name"], "svc");
+        assert_eq!(data["port"], Value::from(8080));
+        assert_eq!(data["features"], serde_json::json!(["api", "cli"]));
+        assert_eq!(data["owner"], serde_json::json!({"team": "core"}));
+    }
+
+    #[rstest]
+    #[case("answers.json", "[1, 2]")]
+    #[case("answers.json", "{not json")]
+    #[case("answers.txt", "name = \"svc\"")]
+    fn load_data_file_rejects(#[case] file_name: &str, #[case] content: &str) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(file_name);
+        std::fs::write(&path, content).unwrap();
+
+        assert!(matches!(
+            load_data_file(&path),
+            Err(DicecutError::DataFileParse { .. })
+        ));
+    }
+}
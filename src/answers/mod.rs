@@ -1,11 +1,19 @@
+pub mod data;
+
 use std::collections::{BTreeMap, HashMap};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use tera::Value;
 
 use crate::config::schema::TemplateConfig;
+use crate::config::variable::VariableConfig;
 use crate::error::{DicecutError, Result};
+use crate::prompt::engine::toml_to_tera_value;
+use crate::template::source::is_git_url;
+
+pub use data::{data_from_env, load_data_file};
 
 pub enum TemplateOrigin {
     Local,
@@ -13,6 +21,8 @@ pub enum TemplateOrigin {
         url: String,
         git_ref: Option<String>,
         commit_sha: Option<String>,
+        /// Subdirectory within the repo that contains the template.
+        subpath: Option<String>,
     },
 }
 
@@ -21,16 +31,62 @@ pub struct SavedAnswers {
     pub template_source: String,
     pub template_ref: Option<String>,
     pub commit_sha: Option<String>,
+    pub template_subpath: Option<String>,
+    /// Template format the project was generated with `--format`, if any.
+    pub format: Option<String>,
     pub diecut_version: String,
     pub answers: HashMap<String, toml::Value>,
 }
 
+impl SavedAnswers {
+    /// Convert the saved answers into typed template values.
+    pub fn values(&self) -> HashMap<String, Value> {
+        self.answers
+            .iter()
+            .map(|(name, value)| (name.clone(), toml_to_tera_value(value)))
+            .collect()
+    }
+}
+
 pub fn load_answers(project_path: &Path) -> Result<SavedAnswers> {
-    let answers_path = project_path.join(".diecut-answers.toml");
-    if !answers_path.exists() {
-        return Err(DicecutError::NoAnswerFile {
+    let answers_path =
+        find_answers_file(project_path).ok_or_else(|| DicecutError::NoAnswerFile {
             path: project_path.to_path_buf(),
-        });
+        })?;
+    load_answers_file(&answers_path)
+}
+
+/// The answers file in a project: `.diecut-answers.toml`, or else a top-level
+/// TOML file with a `[_diecut]` table, for templates that set `[answers] file`.
+pub fn find_answers_file(project_path: &Path) -> Option<PathBuf> {
+    let default_path = project_path.join(".diecut-answers.toml");
+    if default_path.is_file() {
+        return Some(default_path);
+    }
+
+    let mut candidates: Vec<PathBuf> = std::fs::read_dir(project_path)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "toml"))
+        .collect();
+    candidates.sort();
+    candidates.into_iter().find(|path| {
+        std::fs::read_to_string(path)
+            .ok()
+            .and_then(|content| content.parse::<toml::Table>().ok())
+            .is_some_and(|table| table.get("_diecut").is_some_and(toml::Value::is_table))
+    })
+}
+
+/// Load answers from an answers file, or from a project directory containing one.
+pub fn load_answers_file(path: &Path) -> Result<SavedAnswers> {
+    if path.is_dir() {
+        return load_answers(path);
+    }
+    let answers_path = path.to_path_buf();
+    if !answers_path.exists() {
+        return Err(DicecutError::NoAnswerFile { path: answers_path });
     }
 
     let content = std::fs::read_to_string(&answers_path).map_err(|e| DicecutError::Io {
@@ -50,8 +106,11 @@ pub fn load_answers(project_path: &Path) -> Result<SavedAnswers> {
 
     let get_str = |key: &str| -> Option<&str> { meta.get(key).and_then(toml::Value::as_str) };
 
+    // Older answers files kept the source in `template`. Newer ones keep the
+    // template's name there, which is never a git URL, so a project generated
+    // from a local template has no source to reuse.
     let template_source = get_str("template_source")
-        .or_else(|| get_str("template"))
+        .or_else(|| get_str("template").filter(|template| is_git_url(template)))
         .unwrap_or("")
         .to_string();
 
@@ -59,6 +118,10 @@ pub fn load_answers(project_path: &Path) -> Result<SavedAnswers> {
 
     let commit_sha = get_str("commit_sha").map(String::from);
 
+    let template_subpath = get_str("template_subpath").map(String::from);
+
+    let format = get_str("format").map(String::from);
+
     let diecut_version = get_str("diecut_version").unwrap_or("0.0.0").to_string();
 
     let vars_table = table
@@ -73,17 +136,21 @@ pub fn load_answers(project_path: &Path) -> Result<SavedAnswers> {
         template_source,
         template_ref,
         commit_sha,
+        template_subpath,
+        format,
         diecut_version,
         answers,
     })
 }
 
-/// Excludes secret variables. Includes template source metadata for `diecut update`.
+/// Excludes secret variables. Includes template source metadata for `diecut update`,
+/// and the `--format` the template was read with, if one was given.
 pub fn write_answers(
     output_dir: &Path,
     config: &TemplateConfig,
     variables: &BTreeMap<String, Value>,
     origin: &TemplateOrigin,
+    format: Option<&str>,
 ) -> Result<()> {
     let answers_path = output_dir.join(&config.answers.file);
 
@@ -101,6 +168,7 @@ pub fn write_answers(
         url,
         git_ref,
         commit_sha,
+        subpath,
     } = origin
     {
         meta.insert(
@@ -113,6 +181,18 @@ pub fn write_answers(
         if let Some(sha) = commit_sha {
             meta.insert("commit_sha".to_string(), toml::Value::String(sha.clone()));
         }
+        if let Some(sub) = subpath {
+            meta.insert(
+                "template_subpath".to_string(),
+                toml::Value::String(sub.clone()),
+            );
+        }
+    }
+    if let Some(format) = format {
+        meta.insert(
+            "format".to_string(),
+            toml::Value::String(format.to_string()),
+        );
     }
     meta.insert(
         "diecut_version".to_string(),
@@ -127,7 +207,10 @@ pub fn write_answers(
                 continue;
             }
         }
-        if let Some(toml_val) = tera_value_to_toml(value) {
+        if let Some(mut toml_val) = tera_value_to_toml(value) {
+            if let Some(fields) = config.variables.get(name).and_then(|v| v.fields.as_ref()) {
+                strip_secret_fields(&mut toml_val, fields);
+            }
             vars.insert(name.clone(), toml_val);
         }
     }
@@ -146,7 +229,22 @@ pub fn write_answers(
     Ok(())
 }
 
-fn tera_value_to_toml(value: &Value) -> Option<toml::Value> {
+/// Remove secret fields from a table, or from each entry of a group.
+fn strip_secret_fields(value: &mut toml::Value, fields: &IndexMap<String, VariableConfig>) {
+    match value {
+        toml::Value::Table(table) => {
+            table.retain(|key, _| !fields.get(key).is_some_and(|field| field.secret));
+        }
+        toml::Value::Array(entries) => {
+            for entry in entries {
+                strip_secret_fields(entry, fields);
+            }
+        }
+        _ => {}
+    }
+}
+
+pub(crate) fn tera_value_to_toml(value: &Value) -> Option<toml::Value> {
     match value {
         Value::String(s) => Some(toml::Value::String(s.clone())),
         Value::Bool(b) => Some(toml::Value::Boolean(*b)),
@@ -161,7 +259,14 @@ fn tera_value_to_toml(value: &Value) -> Option<toml::Value> {
             let items: Vec<toml::Value> = arr.iter().filter_map(tera_value_to_toml).collect();
             Some(toml::Value::Array(items))
         }
-        _ => None,
+        Value::Object(map) => {
+            let table: toml::Table = map
+                .iter()
+                .filter_map(|(key, value)| Some((key.clone(), tera_value_to_toml(value)?)))
+                .collect();
+            Some(toml::Value::Table(table))
+        }
+        Value::Null => None,
     }
 }
 
@@ -172,6 +277,63 @@ mod tests {
     use super::*;
     use std::fs;
 
+    /// Group and table answers survive a write→read roundtrip, minus secret fields.
+    #[test]
+    fn test_structured_answers_roundtrip() {
+        let output_dir = tempfile::tempdir().unwrap();
+        let config: TemplateConfig = toml::from_str(
+            r#"
+[template]
+name = "structured"
+
+[variables.services]
+type = "group"
+
+[variables.services.fields.name]
+type = "string"
+
+[variables.services.fields.token]
+type = "string"
+secret = true
+
+[variables.owner]
+type = "table"
+
+[variables.owner.fields.team]
+type = "string"
+"#,
+        )
+        .unwrap();
+
+        let mut variables = BTreeMap::new();
+        variables.insert(
+            "services".to_string(),
+            serde_json::json!([
+                {"name": "api", "token": "hunter2"},
+                {"name": "worker", "token": "hunter3"},
+            ]),
+        );
+        variables.insert("owner".to_string(), serde_json::json!({"team": "platform"}));
+
+        write_answers(
+            output_dir.path(),
+            &config,
+            &variables,
+            &TemplateOrigin::Local,
+            None,
+        )
+        .unwrap();
+
+        let content = fs::read_to_string(output_dir.path().join(".diecut-answers.toml")).unwrap();
+        assert!(!content.contains("hunter"));
+        let values = load_answers(output_dir.path()).unwrap().values();
+        assert_eq!(
+            values["services"],
+            serde_json::json!([{"name": "api"}, {"name": "worker"}])
+        );
+        assert_eq!(values["owner"], serde_json::json!({"team": "platform"}));
+    }
+
     /// Integration test: verify write→read roundtrip with multiple types and git metadata
     #[test]
     fn test_write_and_read_answers_roundtrip() {
@@ -183,11 +345,14 @@ mod tests {
                 version: Some("1.2.3".to_string()),
                 description: None,
                 min_diecut_version: None,
+                max_diecut_version: None,
+                deprecated: None,
+                extends: None,
                 templates_suffix: None,
             },
             variables: IndexMap::new(),
             files: crate::config::schema::FilesConfig::default(),
-            hooks: crate::config::schema::HooksConfig { post_create: None },
+            hooks: Default::default(),
             answers: crate::config::schema::AnswersConfig::default(),
         };
 
@@ -203,9 +368,17 @@ mod tests {
             url: "https://example.com/repo.git".to_string(),
             git_ref: Some("v1.0".to_string()),
             commit_sha: Some("deadbeef".to_string()),
+            subpath: Some("templates/rust".to_string()),
         };
 
-        write_answers(output_dir.path(), &config, &variables, &origin).unwrap();
+        write_answers(
+            output_dir.path(),
+            &config,
+            &variables,
+            &origin,
+            Some("copier"),
+        )
+        .unwrap();
 
         // Read back and verify
         let answers_file = output_dir.path().join(".diecut-answers.toml");
@@ -236,6 +409,20 @@ mod tests {
             metadata.get("commit_sha").unwrap().as_str().unwrap(),
             "deadbeef"
         );
+        assert_eq!(
+            metadata.get("template_subpath").unwrap().as_str().unwrap(),
+            "templates/rust"
+        );
+
+        let saved = load_answers(output_dir.path()).unwrap();
+        assert_eq!(saved.template_subpath.as_deref(), Some("templates/rust"));
+        assert_eq!(saved.format.as_deref(), Some("copier"));
+        let values = saved.values();
+        assert_eq!(
+            values.get("count").unwrap(),
+            &Value::Number(serde_json::Number::from(42))
+        );
+        assert_eq!(values.get("enabled").unwrap(), &Value::Bool(true));
     }
 
     /// Integration test: verify secret variables are excluded from answers file
@@ -262,11 +449,14 @@ mod tests {
                 version: None,
                 description: None,
                 min_diecut_version: None,
+                max_diecut_version: None,
+                deprecated: None,
+                extends: None,
                 templates_suffix: None,
             },
             variables: variables_config,
             files: crate::config::schema::FilesConfig::default(),
-            hooks: crate::config::schema::HooksConfig { post_create: None },
+            hooks: Default::default(),
             answers: crate::config::schema::AnswersConfig::default(),
         };
 
@@ -285,6 +475,7 @@ mod tests {
             &config,
             &variables,
             &TemplateOrigin::Local,
+            None,
         )
         .unwrap();
 
@@ -309,4 +500,41 @@ mod tests {
 
         assert!(result.is_err());
     }
+
+    /// A template's `[answers] file` is found by its `[_diecut]` table.
+    #[test]
+    fn test_load_answers_finds_renamed_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join(".answers.toml"),
+            "[_diecut]\ntemplate_source = \"https://github.com/user/tpl.git\"\n\n[variables]\n",
+        )
+        .unwrap();
+
+        let saved = load_answers(temp_dir.path()).unwrap();
+
+        assert_eq!(saved.template_source, "https://github.com/user/tpl.git");
+    }
+
+    /// Answers files from before `template_source` kept the git URL in `template`.
+    #[test]
+    fn test_load_answers_legacy_template_key() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            temp_dir.path().join(".diecut-answers.toml"),
+            "[_diecut]\ntemplate = \"https://github.com/user/tpl.git\"\ntemplate_ref = \"v1\"\n\n[variables]\nname = \"demo\"\n",
+        )
+        .unwrap();
+
+        let saved = load_answers(temp_dir.path()).unwrap();
+
+        assert_eq!(saved.template_source, "https://github.com/user/tpl.git");
+        assert_eq!(saved.template_ref.as_deref(), Some("v1"));
+        assert_eq!(saved.answers["name"].as_str(), Some("demo"));
+    }
 }
@@ -0,0 +1,694 @@
+use std::collections::{BTreeSet, HashMap};
+use std::path::Path;
+
+use globset::{Glob, GlobMatcher, GlobSet, GlobSetBuilder};
+use tera::Tera;
+
+use crate::adapter::{resolve_template_as, ResolvedTemplate};
+use crate::config::schema::DEFAULT_TEMPLATES_SUFFIX;
+use crate::config::variable::{VariableConfig, VariableType};
+use crate::error::Result;
+use crate::prompt::evaluation_order;
+use crate::prompt::order::variable_dependencies;
+use crate::render::file::is_binary_file;
+use crate::render::references::{expression_references, template_references};
+use crate::render::walker::each_glob;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// A single problem found in a template.
+#[derive(Debug)]
+pub struct Finding {
+    pub severity: Severity,
+    /// Where the problem is, e.g. `template/README.md.die` or `variables.license`.
+    pub location: String,
+    pub message: String,
+}
+
+/// Everything `diecut check` found in a template.
+pub struct CheckReport {
+    pub template_name: String,
+    /// The adapter that read the template, e.g. `native` or `cookiecutter`.
+    pub format: String,
+    pub findings: Vec<Finding>,
+}
+
+impl CheckReport {
+    pub fn error_count(&self) -> usize {
+        self.count(Severity::Error)
+    }
+
+    pub fn warning_count(&self) -> usize {
+        self.count(Severity::Warning)
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == severity)
+            .count()
+    }
+
+    fn error(&mut self, location: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Error, location.into(), message.into());
+    }
+
+    fn warning(&mut self, location: impl Into<String>, message: impl Into<String>) {
+        self.push(Severity::Warning, location.into(), message.into());
+    }
+
+    fn push(&mut self, severity: Severity, location: String, message: String) {
+        self.findings.push(Finding {
+            severity,
+            location,
+            message,
+        });
+    }
+}
+
+/// Statically check a template without rendering it.
+///
+/// Errors loading `diecut.toml` are returned as `Err`; everything else is
+/// collected into the report so all problems are shown at once.
+pub fn check_template(template_dir: &Path) -> Result<CheckReport> {
+    check_template_as(template_dir, None)
+}
+
+/// [`check_template`], reading the template with the adapter named `format`
+/// instead of detecting it.
+pub fn check_template_as(template_dir: &Path, format: Option<&str>) -> Result<CheckReport> {
+    let resolved = resolve_template_as(template_dir, format)?;
+    let mut report = CheckReport {
+        template_name: resolved.config.template.name.clone(),
+        format: resolved.format.clone(),
+        findings: Vec::new(),
+    };
+
+    for warning in &resolved.warnings {
+        report.warning("diecut.toml", warning.clone());
+    }
+
+    let files = collect_files(&resolved, &mut report);
+    let partials = collect_partials(&resolved, &mut report);
+    check_templates_parse(&files, &partials, &mut report);
+    check_variables(&resolved, &mut report);
+    if let Err(e) = evaluation_order(&resolved.config) {
+        report.error("variables", e.to_string());
+    }
+    check_file_patterns(&resolved, &files, &mut report);
+    check_unused_variables(&resolved, &files, &partials, &mut report);
+
+    report.findings.sort_by_key(|f| f.severity);
+    Ok(report)
+}
+
+/// A file in the template content directory.
+struct TemplateFile {
+    /// Path relative to the content directory, as written in the template.
+    rel_path: String,
+    /// The same path with the template suffix removed from the file name.
+    output_path: String,
+    /// Source text, for files rendered through Tera and partials.
+    source: Option<String>,
+    /// Whether the file matches `files.partials` (loaded into Tera, not written out).
+    is_partial: bool,
+}
+
+fn collect_files(resolved: &ResolvedTemplate, report: &mut CheckReport) -> Vec<TemplateFile> {
+    let content_files = match resolved.content_files() {
+        Ok(files) => files,
+        Err(e) => {
+            report.error("template/", e.to_string());
+            return Vec::new();
+        }
+    };
+
+    let suffix = resolved
+        .config
+        .template
+        .templates_suffix
+        .as_deref()
+        .unwrap_or(DEFAULT_TEMPLATES_SUFFIX);
+
+    let mut partial_set = GlobSetBuilder::new();
+    for pattern in &resolved.config.files.partials {
+        // Invalid patterns are reported by check_file_patterns.
+        if let Ok(glob) = Glob::new(pattern) {
+            partial_set.add(glob);
+        }
+    }
+    let partial_set = partial_set.build().unwrap_or_else(|_| GlobSet::empty());
+
+    let mut files = Vec::new();
+    for file in content_files {
+        let rel_path = file.rel_path.to_string_lossy().into_owned();
+        let is_partial = partial_set.is_match(&rel_path);
+        // An empty suffix renders every text file, as in Cookiecutter templates.
+        let is_template = rel_path.ends_with(suffix) && !is_binary_file(&file.src_path);
+
+        let source = if is_template || is_partial {
+            match std::fs::read_to_string(&file.src_path) {
+                Ok(content) => Some(content),
+                Err(e) => {
+                    report.error(template_location(&rel_path), format!("unreadable: {e}"));
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let output_path = if is_template && !is_partial {
+            rel_path[..rel_path.len() - suffix.len()].to_string()
+        } else {
+            rel_path.clone()
+        };
+
+        files.push(TemplateFile {
+            rel_path,
+            output_path,
+            source,
+            is_partial,
+        });
+    }
+    files
+}
+
+/// Sources of the files in `partials/`, keyed by Tera name.
+fn collect_partials(
+    resolved: &ResolvedTemplate,
+    report: &mut CheckReport,
+) -> Vec<(String, String)> {
+    let mut partials = Vec::new();
+    for (name, path) in resolved.partial_files() {
+        match std::fs::read_to_string(&path) {
+            Ok(source) => partials.push((name, source)),
+            Err(e) => report.error(format!("partials/{name}"), format!("unreadable: {e}")),
+        }
+    }
+    partials
+}
+
+fn check_templates_parse(
+    files: &[TemplateFile],
+    partials: &[(String, String)],
+    report: &mut CheckReport,
+) {
+    let mut sources: Vec<(String, &str)> = partials
+        .iter()
+        .map(|(name, source)| (name.clone(), source.as_str()))
+        .collect();
+    for file in files.iter().filter(|f| f.is_partial) {
+        if let Some(source) = &file.source {
+            sources.push((file.rel_path.replace('\\', "/"), source));
+        }
+    }
+
+    let mut shared = Tera::default();
+    if let Err(e) = shared.add_raw_templates(sources) {
+        report.error("partials", error_chain(&e));
+        shared = Tera::default();
+    }
+
+    for file in files {
+        if file.rel_path.contains("{{") || file.rel_path.contains("{%") {
+            if let Err(e) = shared
+                .clone()
+                .add_raw_template(&file.rel_path, &file.rel_path)
+            {
+                report.error(
+                    template_location(&file.rel_path),
+                    format!("invalid template expression in path: {}", error_chain(&e)),
+                );
+            }
+        }
+        if let Some(source) = file.source.as_ref().filter(|_| !file.is_partial) {
+            if let Err(e) = shared.clone().add_raw_template(&file.rel_path, source) {
+                report.error(template_location(&file.rel_path), error_chain(&e));
+            }
+        }
+    }
+}
+
+fn check_variables(resolved: &ResolvedTemplate, report: &mut CheckReport) {
+    for (name, var) in &resolved.config.variables {
+        let location = format!("variables.{name}");
+
+        if let Some(pattern) = &var.validation {
+            match regex_lite::Regex::new(pattern) {
+                Ok(re) => {
+                    if let Some(toml::Value::String(default)) = &var.default {
+                        if var.templated_default().is_none() && !re.is_match(default) {
+                            report.error(
+                                &location,
+                                format!(
+                                    "default '{default}' does not match validation '{pattern}'"
+                                ),
+                            );
+                        }
+                    }
+                }
+                Err(e) => report.error(&location, format!("invalid validation regex: {e}")),
+            }
+        }
+
+        if let Some(when) = &var.when {
+            let wrapped = format!("{{% if {when} %}}{{% endif %}}");
+            if let Err(e) = Tera::default().add_raw_template("when", &wrapped) {
+                report.error(
+                    &location,
+                    format!("invalid `when` expression: {}", error_chain(&e)),
+                );
+            }
+        }
+
+        if let Some(computed) = &var.computed {
+            if let Err(e) = Tera::default().add_raw_template("computed", computed) {
+                report.error(
+                    &location,
+                    format!("invalid `computed` expression: {}", error_chain(&e)),
+                );
+            }
+        }
+
+        if let Some(template) = var.templated_default() {
+            if let Err(e) = Tera::default().add_raw_template("default", template) {
+                report.error(
+                    &location,
+                    format!("invalid templated default: {}", error_chain(&e)),
+                );
+            }
+        } else if let Some(default) = &var.default {
+            if let Err(reason) = check_default(var, default) {
+                report.error(&location, format!("default {default}: {reason}"));
+            }
+        }
+    }
+}
+
+/// Check that a default has the variable's type and, for selects, is one of the choices.
+fn check_default(var: &VariableConfig, default: &toml::Value) -> std::result::Result<(), String> {
+    let check_choice = |value: &str| {
+        if var.choices_from.is_some() || var.has_choice(value) {
+            Ok(())
+        } else {
+            Err(format!("'{value}' is not one of the choices"))
+        }
+    };
+
+    match (&var.var_type, default) {
+        (VariableType::String, toml::Value::String(_))
+        | (VariableType::Bool, toml::Value::Boolean(_))
+        | (VariableType::Int, toml::Value::Integer(_))
+        | (VariableType::Float, toml::Value::Float(_) | toml::Value::Integer(_)) => Ok(()),
+        (VariableType::Select, toml::Value::String(s)) => check_choice(s),
+        (VariableType::List, toml::Value::Array(items)) => {
+            items.iter().try_for_each(|item| match item {
+                toml::Value::String(_) => Ok(()),
+                other => Err(format!("{other} is not a string")),
+            })
+        }
+        (VariableType::Table, toml::Value::Table(_))
+        | (VariableType::Group, toml::Value::Array(_)) => Ok(()),
+        (VariableType::Multiselect, toml::Value::Array(items)) => {
+            items.iter().try_for_each(|item| match item {
+                toml::Value::String(s) => check_choice(s),
+                other => Err(format!("{other} is not a string")),
+            })
+        }
+        (var_type, _) => Err(format!("does not match type {}", var_type.as_str())),
+    }
+}
+
+fn check_file_patterns(
+    resolved: &ResolvedTemplate,
+    files: &[TemplateFile],
+    report: &mut CheckReport,
+) {
+    let config = &resolved.config.files;
+    let lists = [
+        ("files.exclude", &config.exclude),
+        ("files.copy_without_render", &config.copy_without_render),
+        ("files.partials", &config.partials),
+    ];
+    for (key, patterns) in lists {
+        for (i, pattern) in patterns.iter().enumerate() {
+            check_glob(&format!("{key}[{i}]"), pattern, files, report);
+        }
+    }
+
+    for (i, cond) in config.conditional.iter().enumerate() {
+        let location = format!("files.conditional[{i}]");
+        check_glob(&location, &cond.pattern, files, report);
+
+        let wrapped = format!("{{% if {} %}}{{% endif %}}", cond.when);
+        if let Err(e) = Tera::default().add_raw_template("when", &wrapped) {
+            report.error(
+                &location,
+                format!("invalid `when` expression: {}", error_chain(&e)),
+            );
+        }
+    }
+
+    for (i, rule) in config.each.iter().enumerate() {
+        let location = format!("files.each[{i}]");
+        match each_glob(&rule.pattern) {
+            Ok(glob) => warn_if_unmatched(
+                &location,
+                &rule.pattern,
+                &glob.compile_matcher(),
+                files,
+                report,
+            ),
+            Err(e) => report.error(&location, e.to_string()),
+        }
+    }
+}
+
+fn check_glob(location: &str, pattern: &str, files: &[TemplateFile], report: &mut CheckReport) {
+    match Glob::new(pattern) {
+        Ok(glob) => warn_if_unmatched(location, pattern, &glob.compile_matcher(), files, report),
+        Err(e) => report.error(location, format!("invalid glob '{pattern}': {e}")),
+    }
+}
+
+fn warn_if_unmatched(
+    location: &str,
+    pattern: &str,
+    matcher: &GlobMatcher,
+    files: &[TemplateFile],
+    report: &mut CheckReport,
+) {
+    let matches_any = files
+        .iter()
+        .any(|f| matcher.is_match(&f.rel_path) || matcher.is_match(&f.output_path));
+    if !matches_any {
+        report.warning(
+            location,
+            format!("glob '{pattern}' does not match any file"),
+        );
+    }
+}
+
+fn check_unused_variables(
+    resolved: &ResolvedTemplate,
+    files: &[TemplateFile],
+    partials: &[(String, String)],
+    report: &mut CheckReport,
+) {
+    let mut used: BTreeSet<String> = BTreeSet::new();
+    for (_, source) in partials {
+        used.extend(template_references(source));
+    }
+    for file in files {
+        used.extend(template_references(&file.rel_path));
+        if let Some(source) = &file.source {
+            used.extend(template_references(source));
+        }
+    }
+    for cond in &resolved.config.files.conditional {
+        used.extend(expression_references(&cond.when));
+    }
+    for rule in &resolved.config.files.each {
+        used.insert(rule.over.clone());
+    }
+    if let Some(namespace) = &resolved.namespace {
+        let attribute = regex_lite::Regex::new(&format!(
+            r"\b{}\.([A-Za-z_][A-Za-z0-9_]*)",
+            regex_lite::escape(namespace)
+        ))
+        .expect("valid regex");
+        // The content root's own name, e.g. `{{cookiecutter.project_slug}}`.
+        let roots: Vec<String> = resolved
+            .content_dirs
+            .iter()
+            .filter_map(|dir| Some(dir.file_name()?.to_string_lossy().into_owned()))
+            .collect();
+        let sources = partials
+            .iter()
+            .map(|(_, source)| source.as_str())
+            .chain(roots.iter().map(String::as_str))
+            .chain(files.iter().map(|f| f.rel_path.as_str()))
+            .chain(files.iter().filter_map(|f| f.source.as_deref()));
+        for source in sources {
+            used.extend(attribute.captures_iter(source).map(|c| c[1].to_string()));
+        }
+    }
+
+    // A variable's own expressions do not count as a use of itself.
+    let used_by_variables: HashMap<&str, BTreeSet<String>> = resolved
+        .config
+        .variables
+        .iter()
+        .map(|(name, var)| {
+            (
+                name.as_str(),
+                variable_dependencies(name, var, &resolved.config),
+            )
+        })
+        .collect();
+
+    for name in resolved.config.variables.keys() {
+        let referenced = used.contains(name)
+            || used_by_variables
+                .iter()
+                .any(|(_, refs)| refs.contains(name));
+        if !referenced {
+            report.warning(
+                format!("variables.{name}"),
+                "not referenced by any template file, path, or other variable",
+            );
+        }
+    }
+}
+
+fn template_location(rel_path: &str) -> String {
+    format!("template/{rel_path}")
+}
+
+/// Join a Tera error with its causes, which hold the actual parse message.
+fn error_chain(error: &tera::Error) -> String {
+    let mut message = error.to_string();
+    let mut source = std::error::Error::source(error);
+    while let Some(cause) = source {
+        message.push_str(": ");
+        message.push_str(&cause.to_string());
+        source = cause.source();
+    }
+    message
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::native_template;
+    use std::fs;
+
+    fn locations(report: &CheckReport, severity: Severity) -> Vec<&str> {
+        report
+            .findings
+            .iter()
+            .filter(|f| f.severity == severity)
+            .map(|f| f.location.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn clean_template_has_no_findings() {
+        let dir = native_template(
+            r#"
+[template]
+name = "clean"
+
+[variables.project_name]
+type = "string"
+default = "demo"
+validation = "^[a-z]+$"
+
+[variables.license]
+type = "select"
+choices = ["MIT", "Apache-2.0"]
+default = "MIT"
+
+[files]
+exclude = ["*.bak"]
+"#,
+            &[
+                (
+                    "{{project_name}}/README.md.die",
+                    "# {{ project_name }} ({{ license }})",
+                ),
+                ("notes.bak", "scratch"),
+            ],
+        );
+
+        let report = check_template(dir.path()).unwrap();
+        assert!(report.findings.is_empty(), "{:?}", report.findings);
+    }
+
+    #[test]
+    fn reports_every_problem_at_once() {
+        let dir = native_template(
+            r#"
+[template]
+name = "broken"
+
+[variables.name]
+type = "string"
+validation = "([a-z"
+
+[variables.count]
+type = "int"
+default = "three"
+
+[variables.license]
+type = "select"
+choices = ["MIT"]
+default = "GPL"
+
+[variables.unused]
+type = "bool"
+
+[files]
+copy_without_render = ["assets/**"]
+conditional = [{ pattern = "[", when = "count > 1" }]
+"#,
+            &[(
+                "main.rs.die",
+                "{{ name }} {{ count }} {{ license }} {% if %}",
+            )],
+        );
+
+        let report = check_template(dir.path()).unwrap();
+        let errors = locations(&report, Severity::Error);
+        assert!(errors.contains(&"template/main.rs.die"), "{errors:?}");
+        assert!(errors.contains(&"variables.name"), "{errors:?}");
+        assert!(errors.contains(&"variables.count"), "{errors:?}");
+        assert!(errors.contains(&"variables.license"), "{errors:?}");
+        assert!(errors.contains(&"files.conditional[0]"), "{errors:?}");
+
+        let warnings = locations(&report, Severity::Warning);
+        assert_eq!(
+            warnings,
+            vec!["files.copy_without_render[0]", "variables.unused"]
+        );
+        assert_eq!(report.error_count(), 5);
+    }
+
+    #[test]
+    fn files_each_patterns_are_checked_and_count_as_use() {
+        let dir = native_template(
+            r#"
+[template]
+name = "each"
+
+[variables.models]
+type = "list"
+
+[[files.each]]
+pattern = "src/{{ item }}.rs.die"
+over = "models"
+
+[[files.each]]
+pattern = "docs/{{ item }}.md.die"
+over = "models"
+"#,
+            &[("src/{{ item }}.rs.die", "struct {{ item }};")],
+        );
+
+        let report = check_template(dir.path()).unwrap();
+        assert_eq!(report.error_count(), 0, "{:?}", report.findings);
+        assert_eq!(locations(&report, Severity::Warning), vec!["files.each[1]"]);
+    }
+
+    #[test]
+    fn cookiecutter_namespace_references_count_as_use() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("cookiecutter.json"),
+            r#"{"project_slug": "demo", "author": "me", "unused": "x"}"#,
+        )
+        .unwrap();
+        let project = dir.path().join("{{cookiecutter.project_slug}}");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join("README.md"), "by {{ cookiecutter.author }}").unwrap();
+
+        let report = check_template(dir.path()).unwrap();
+        assert_eq!(report.error_count(), 0, "{:?}", report.findings);
+        assert_eq!(
+            locations(&report, Severity::Warning),
+            vec!["variables.unused"]
+        );
+    }
+
+    #[test]
+    fn variables_used_only_by_other_variables_count_as_used() {
+        let dir = native_template(
+            r#"
+[template]
+name = "chained"
+
+[variables.project_name]
+type = "string"
+
+[variables.use_ci]
+type = "bool"
+when = "project_name != ''"
+
+[variables.slug]
+type = "string"
+computed = "{{ project_name | slugify }}"
+when = "use_ci"
+"#,
+            &[("README.md.die", "{{ slug }}")],
+        );
+
+        let report = check_template(dir.path()).unwrap();
+        assert!(report.findings.is_empty(), "{:?}", report.findings);
+    }
+
+    #[test]
+    fn multiselect_default_must_use_choices() {
+        let var = VariableConfig {
+            var_type: VariableType::Multiselect,
+            choices: Some(vec!["a".into(), "b".into()]),
+            ..Default::default()
+        };
+        let ok = toml::Value::Array(vec![toml::Value::String("a".into())]);
+        let bad = toml::Value::Array(vec![toml::Value::String("c".into())]);
+        assert!(check_default(&var, &ok).is_ok());
+        assert!(check_default(&var, &bad).is_err());
+    }
+
+    #[test]
+    fn templated_defaults_are_parsed_not_type_checked() {
+        let dir = native_template(
+            r#"
+[template]
+name = "templated"
+
+[variables.base_port]
+type = "int"
+default = 8080
+
+[variables.admin_port]
+type = "int"
+default = "{{ base_port + 1 }}"
+
+[variables.crate_name]
+type = "string"
+default = "{{ base_port | broken("
+"#,
+            &[("ports.txt", "{{ admin_port }} {{ crate_name }}")],
+        );
+        let report = check_template(dir.path()).unwrap();
+        assert_eq!(
+            locations(&report, Severity::Error),
+            vec!["variables.crate_name"]
+        );
+    }
+}
@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 use clap::{Parser, Subcommand};
 use diecut::template::GitProtocol;
 
@@ -16,21 +18,45 @@ pub struct Cli {
 pub enum Commands {
     /// Generate a new project from a template
     New {
-        /// Template source (local path, or in future: git URL / abbreviation)
-        template: String,
+        /// Template source (local path, git URL, or abbreviation).
+        /// Optional when --answers-file records the template source.
+        #[arg(required_unless_present = "answers_file")]
+        template: Option<String>,
+
+        /// Git branch, tag, or commit SHA to use (also: gh:user/repo@REF)
+        #[arg(long = "ref", value_name = "REF")]
+        git_ref: Option<String>,
+
+        /// Read the template as this format (native, cookiecutter, copier)
+        /// instead of detecting it
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<String>,
 
         /// Output directory
         #[arg(short, long)]
         output: Option<String>,
 
+        /// Reuse answers from a .diecut-answers.toml file (or a project directory containing one)
+        #[arg(long, value_name = "PATH")]
+        answers_file: Option<String>,
+
         /// Set variable values (can be repeated: -d key=value)
         #[arg(short, long = "data", value_name = "KEY=VALUE")]
         data: Vec<String>,
 
+        /// Read variable values from a JSON, TOML, or YAML file
+        #[arg(long, value_name = "PATH")]
+        data_file: Option<String>,
+
         /// Use default values without prompting
         #[arg(long)]
         defaults: bool,
 
+        /// Never prompt; fail listing any variables without a value.
+        /// Implied when stdin is not a terminal.
+        #[arg(long)]
+        no_input: bool,
+
         /// Overwrite output directory if it exists
         #[arg(long)]
         overwrite: bool,
@@ -48,13 +74,188 @@ pub enum Commands {
         verbose: bool,
 
         /// Protocol for expanding shortcodes (ssh or https).
-        /// Defaults to ssh. Override with DIECUT_GIT_PROTOCOL env var.
+        /// Defaults to ssh. Override with DIECUT_GIT_PROTOCOL env var or
+        /// `diecut config set protocol https`.
         #[arg(long, value_enum)]
         protocol: Option<GitProtocol>,
     },
 
     /// List cached templates
     List,
+
+    /// Manage the template cache
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+
+    /// Regenerate a project from its saved answers without prompting
+    Replay {
+        /// Project directory containing .diecut-answers.toml
+        project: String,
+
+        /// Template source (defaults to the one recorded in the answers file)
+        #[arg(short, long)]
+        template: Option<String>,
+
+        /// Output directory. Without it, the project is regenerated in place
+        /// (needs --overwrite).
+        #[arg(short, long)]
+        output: Option<String>,
+
+        /// Override saved values (can be repeated: -d key=value)
+        #[arg(short, long = "data", value_name = "KEY=VALUE")]
+        data: Vec<String>,
+
+        /// Write into the output directory (the project itself without --output)
+        /// if it isn't empty
+        #[arg(long)]
+        overwrite: bool,
+
+        /// Skip running hooks
+        #[arg(long)]
+        no_hooks: bool,
+
+        /// Show what would be generated without writing files
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Show file contents (with --dry-run) or detailed output
+        #[arg(short, long)]
+        verbose: bool,
+    },
+
+    /// Update a generated project to the latest version of its template
+    Update {
+        /// Project directory (defaults to the current directory)
+        path: Option<String>,
+
+        /// Git branch, tag, or commit SHA to update to (defaults to the recorded ref)
+        #[arg(long = "ref", value_name = "REF")]
+        git_ref: Option<String>,
+
+        /// Use default values for new variables without prompting
+        #[arg(long)]
+        defaults: bool,
+
+        /// Never prompt; fail listing any new variables without a value.
+        /// Implied when stdin is not a terminal.
+        #[arg(long)]
+        no_input: bool,
+    },
+
+    /// Check a template for errors before publishing it
+    Check {
+        /// Template directory (defaults to the current directory)
+        path: Option<String>,
+
+        /// Read the template as this format (native, cookiecutter, copier)
+        /// instead of detecting it
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<String>,
+    },
+
+    /// Turn a Cookiecutter, Copier, or cargo-generate template into a native one
+    Convert {
+        /// Template directory to convert
+        template: String,
+
+        /// Directory to write diecut.toml, template/, and MIGRATION.md to
+        #[arg(short, long)]
+        output: String,
+
+        /// Read the template as this format (cookiecutter, copier, cargo-generate)
+        /// instead of detecting it
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<String>,
+
+        /// Replace an earlier conversion in the output directory
+        #[arg(long)]
+        overwrite: bool,
+    },
+
+    /// Read or change user settings in the diecut config file
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommand {
+    /// Print the value of a setting
+    Get {
+        /// Setting name, e.g. protocol or abbreviations.company
+        key: String,
+    },
+
+    /// Change a setting, keeping the rest of the file intact
+    Set {
+        /// Setting name, e.g. protocol or abbreviations.company
+        key: String,
+
+        /// New value
+        value: String,
+    },
+
+    /// Print every setting in the config file
+    List,
+
+    /// Print the location of the config file
+    Path,
+}
+
+#[derive(Subcommand)]
+pub enum CacheCommand {
+    /// Remove cached templates (all of them, or only those for URL)
+    Clear {
+        /// Template URL or abbreviation to remove
+        url: Option<String>,
+    },
+
+    /// Remove cached templates older than a given age
+    Prune {
+        /// Maximum age to keep, e.g. 30d, 12h, 2w
+        #[arg(long, value_name = "AGE", value_parser = parse_duration)]
+        older_than: Duration,
+    },
+
+    /// Re-clone cached templates (all of them, or only those for URL)
+    Refresh {
+        /// Template URL or abbreviation to refresh
+        url: Option<String>,
+    },
+
+    /// Show cached templates with their size and commit
+    Info,
+}
+
+/// Parse a duration like `30d`, `12h`, `45m`, `90s`, or `2w`.
+fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    let (digits, unit) = input.split_at(split);
+    let amount: u64 = digits
+        .parse()
+        .map_err(|_| format!("invalid duration '{input}': expected e.g. 30d"))?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" | "" => 86400,
+        "w" => 7 * 86400,
+        other => {
+            return Err(format!(
+                "unknown duration unit '{other}': use s, m, h, d, or w"
+            ))
+        }
+    };
+    amount
+        .checked_mul(seconds)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("duration '{input}' is too large"))
 }
 
 #[cfg(test)]
@@ -63,6 +264,26 @@ mod tests {
     use clap::Parser;
     use diecut::template::GitProtocol;
 
+    #[test]
+    fn parses_config_set() {
+        let cli = Cli::parse_from([
+            "diecut",
+            "config",
+            "set",
+            "abbreviations.company",
+            "https://git.company.com/{}.git",
+        ]);
+        if let Commands::Config {
+            command: ConfigCommand::Set { key, value },
+        } = cli.command
+        {
+            assert_eq!(key, "abbreviations.company");
+            assert_eq!(value, "https://git.company.com/{}.git");
+        } else {
+            panic!("expected Config Set");
+        }
+    }
+
     #[test]
     fn parses_new_without_protocol() {
         let cli = Cli::parse_from(["diecut", "new", "gh:user/repo"]);
@@ -93,6 +314,125 @@ mod tests {
         }
     }
 
+    #[test]
+    fn parses_new_with_ref() {
+        let cli = Cli::parse_from(["diecut", "new", "gh:org/tpl", "--ref", "v2.1.0"]);
+        if let Commands::New { git_ref, .. } = cli.command {
+            assert_eq!(git_ref.as_deref(), Some("v2.1.0"));
+        } else {
+            panic!("expected New");
+        }
+    }
+
+    #[test]
+    fn parses_new_with_no_input() {
+        let cli = Cli::parse_from(["diecut", "new", "gh:org/tpl", "--no-input"]);
+        assert!(matches!(cli.command, Commands::New { no_input: true, .. }));
+    }
+
+    #[test]
+    fn parses_new_with_format() {
+        let cli = Cli::parse_from(["diecut", "new", "./tpl", "--format", "copier"]);
+        if let Commands::New { format, .. } = cli.command {
+            assert_eq!(format.as_deref(), Some("copier"));
+        } else {
+            panic!("expected New");
+        }
+    }
+
+    #[test]
+    fn parses_convert() {
+        let cli = Cli::parse_from(["diecut", "convert", "./cc", "-o", "./native"]);
+        if let Commands::Convert {
+            template,
+            output,
+            format,
+            overwrite,
+        } = cli.command
+        {
+            assert_eq!(template, "./cc");
+            assert_eq!(output, "./native");
+            assert!(format.is_none());
+            assert!(!overwrite);
+        } else {
+            panic!("expected Convert");
+        }
+        assert!(Cli::try_parse_from(["diecut", "convert", "./cc"]).is_err());
+    }
+
+    #[test]
+    fn new_requires_template_or_answers_file() {
+        assert!(Cli::try_parse_from(["diecut", "new"]).is_err());
+
+        let cli = Cli::parse_from([
+            "diecut",
+            "new",
+            "--answers-file",
+            "proj/.diecut-answers.toml",
+        ]);
+        if let Commands::New {
+            template,
+            answers_file,
+            ..
+        } = cli.command
+        {
+            assert!(template.is_none());
+            assert_eq!(answers_file.as_deref(), Some("proj/.diecut-answers.toml"));
+        } else {
+            panic!("expected New");
+        }
+    }
+
+    #[test]
+    fn parses_replay() {
+        let cli = Cli::parse_from(["diecut", "replay", "my-project", "-o", "fresh"]);
+        if let Commands::Replay {
+            project, output, ..
+        } = cli.command
+        {
+            assert_eq!(project, "my-project");
+            assert_eq!(output.as_deref(), Some("fresh"));
+        } else {
+            panic!("expected Replay");
+        }
+    }
+
+    #[test]
+    fn parses_update_with_path() {
+        let cli = Cli::parse_from(["diecut", "update", "my-project", "--defaults"]);
+        if let Commands::Update { path, defaults, .. } = cli.command {
+            assert_eq!(path.as_deref(), Some("my-project"));
+            assert!(defaults);
+        } else {
+            panic!("expected Update");
+        }
+    }
+
+    #[test]
+    fn parses_cache_prune() {
+        let cli = Cli::parse_from(["diecut", "cache", "prune", "--older-than", "30d"]);
+        if let Commands::Cache {
+            command: CacheCommand::Prune { older_than },
+        } = cli.command
+        {
+            assert_eq!(older_than, Duration::from_secs(30 * 86400));
+        } else {
+            panic!("expected cache prune");
+        }
+    }
+
+    #[test]
+    fn parse_duration_units() {
+        assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("45m"), Ok(Duration::from_secs(45 * 60)));
+        assert_eq!(parse_duration("12h"), Ok(Duration::from_secs(12 * 3600)));
+        assert_eq!(parse_duration("2w"), Ok(Duration::from_secs(14 * 86400)));
+        assert_eq!(parse_duration("7"), Ok(Duration::from_secs(7 * 86400)));
+        assert!(parse_duration("d").is_err());
+        assert!(parse_duration("3y").is_err());
+        assert!(parse_duration("99999999999999999d").is_err());
+    }
+
     #[test]
     fn rejects_invalid_protocol() {
         let result = Cli::try_parse_from(["diecut", "new", "gh:user/repo", "--protocol", "ftp"]);
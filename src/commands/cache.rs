@@ -0,0 +1,143 @@
+use console::style;
+use miette::Result;
+
+use diecut::config::UserConfig;
+use diecut::template::{
+    cache_entry_size, clear_cache, list_cached, prune_cache, refresh_cache, resolve_git_protocol,
+    resolve_source, ResolveOptions, TemplateSource,
+};
+
+use crate::cli::CacheCommand;
+use crate::commands::list::format_timestamp;
+
+pub fn run(command: CacheCommand, user_config: &UserConfig) -> Result<()> {
+    match command {
+        CacheCommand::Clear { url } => clear(url, user_config),
+        CacheCommand::Prune { older_than } => {
+            let removed = prune_cache(older_than)?;
+            for entry in &removed {
+                println!("  {} {}", style("removed").red(), entry.metadata.url);
+            }
+            println!(
+                "{} Pruned {} cached template{}",
+                style("✓").green().bold(),
+                removed.len(),
+                if removed.len() == 1 { "" } else { "s" }
+            );
+            Ok(())
+        }
+        CacheCommand::Refresh { url } => refresh(url, user_config),
+        CacheCommand::Info => info(),
+    }
+}
+
+fn clear(url: Option<String>, user_config: &UserConfig) -> Result<()> {
+    match url {
+        Some(url) => {
+            let url = expand_url(&url, user_config)?;
+            clear_cache(Some(&url))?;
+            println!(
+                "{} Cleared cached entries for {}",
+                style("✓").green().bold(),
+                url
+            );
+        }
+        None => {
+            clear_cache(None)?;
+            println!("{} Cleared the template cache", style("✓").green().bold());
+        }
+    }
+    Ok(())
+}
+
+fn refresh(url: Option<String>, user_config: &UserConfig) -> Result<()> {
+    let url = url.map(|u| expand_url(&u, user_config)).transpose()?;
+    let refreshed = refresh_cache(url.as_deref())?;
+
+    if refreshed.is_empty() {
+        println!("No cached templates to refresh.");
+        return Ok(());
+    }
+
+    for entry in &refreshed {
+        let sha = entry.metadata.commit_sha.as_deref().unwrap_or("unknown");
+        println!(
+            "  {} {} @ {}",
+            style("refreshed").green(),
+            entry.metadata.url,
+            &sha[..sha.len().min(12)]
+        );
+    }
+    Ok(())
+}
+
+fn info() -> Result<()> {
+    let entries = list_cached()?;
+
+    if entries.is_empty() {
+        println!("No cached templates.");
+        return Ok(());
+    }
+
+    let mut total = 0;
+    for entry in &entries {
+        let size = cache_entry_size(&entry.path);
+        total += size;
+
+        println!("  {} {}", style("source:").dim(), entry.metadata.url);
+        println!(
+            "  {}    {}",
+            style("ref:").dim(),
+            entry.metadata.git_ref.as_deref().unwrap_or("default")
+        );
+        println!(
+            "  {} {}",
+            style("commit:").dim(),
+            entry.metadata.commit_sha.as_deref().unwrap_or("unknown")
+        );
+        println!("  {}   {}", style("size:").dim(), format_size(size));
+        println!(
+            "  {} {}",
+            style("cached:").dim(),
+            format_timestamp(&entry.metadata.cached_at)
+        );
+        println!("  {}   {}", style("path:").dim(), entry.path.display());
+        println!();
+    }
+
+    println!(
+        "{} template{}, {} total",
+        entries.len(),
+        if entries.len() == 1 { "" } else { "s" },
+        format_size(total)
+    );
+    Ok(())
+}
+
+/// Expand abbreviations like `gh:user/repo` so they match the cached URL.
+fn expand_url(input: &str, user_config: &UserConfig) -> Result<String> {
+    let options = ResolveOptions {
+        protocol: resolve_git_protocol(None, user_config.protocol.as_deref())?,
+        user_abbreviations: Some(&user_config.abbreviations),
+        ..Default::default()
+    };
+    match resolve_source(input, options) {
+        Ok(TemplateSource::Git { url, .. }) => Ok(url),
+        _ => Ok(input.to_string()),
+    }
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
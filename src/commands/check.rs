@@ -0,0 +1,61 @@
+use std::path::PathBuf;
+
+use console::style;
+use miette::Result;
+
+use diecut::check::{check_template_as, Severity};
+use diecut::error::DicecutError;
+
+pub fn run(path: Option<String>, format: Option<String>) -> Result<()> {
+    let template_dir = match path {
+        Some(p) => PathBuf::from(p),
+        None => std::env::current_dir().map_err(|e| DicecutError::Io {
+            context: "getting current directory".into(),
+            source: e,
+        })?,
+    };
+
+    let report = check_template_as(&template_dir, format.as_deref())?;
+    println!(
+        "{} {} {}",
+        style("Checking").bold(),
+        report.template_name,
+        style(format!("({} template)", report.format)).dim()
+    );
+
+    for finding in &report.findings {
+        let label = match finding.severity {
+            Severity::Error => style("error:  ").red().bold(),
+            Severity::Warning => style("warning:").yellow().bold(),
+        };
+        println!(
+            "  {} {} {}",
+            label,
+            style(&finding.location).cyan(),
+            finding.message
+        );
+    }
+
+    let errors = report.error_count();
+    let warnings = report.warning_count();
+    if errors > 0 {
+        return Err(DicecutError::CheckFailed { errors }.into());
+    }
+
+    if warnings == 0 {
+        println!(
+            "{} {} has no problems",
+            style("✓").green().bold(),
+            report.template_name
+        );
+    } else {
+        println!(
+            "\n{} {} passed with {} warning{}",
+            style("✓").green().bold(),
+            report.template_name,
+            warnings,
+            if warnings == 1 { "" } else { "s" }
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use miette::Result;
+
+use diecut::config::{
+    get_user_config_value, list_user_config_values, set_user_config_value, user_config_path,
+};
+
+use crate::cli::ConfigCommand;
+
+pub fn run(command: ConfigCommand) -> Result<()> {
+    match command {
+        ConfigCommand::Get { key } => {
+            if let Some(value) = get_user_config_value(&key)? {
+                println!("{value}");
+            }
+        }
+        ConfigCommand::Set { key, value } => {
+            set_user_config_value(&key, &value)?;
+        }
+        ConfigCommand::List => {
+            for (key, value) in list_user_config_values()? {
+                println!("{key} = {value}");
+            }
+        }
+        ConfigCommand::Path => println!("{}", user_config_path()?.display()),
+    }
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use std::path::PathBuf;
+
+use console::style;
+use miette::Result;
+
+use diecut::convert::{convert_template, REPORT_FILE};
+
+pub fn run(
+    template: String,
+    output: String,
+    format: Option<String>,
+    overwrite: bool,
+) -> Result<()> {
+    let output_dir = PathBuf::from(output);
+    let report = convert_template(
+        &PathBuf::from(template),
+        &output_dir,
+        format.as_deref(),
+        overwrite,
+    )?;
+
+    println!(
+        "{} {} from a {} template ({} file{})",
+        style("Converted").green().bold(),
+        report.template_name,
+        report.format,
+        report.files.len(),
+        if report.files.len() == 1 { "" } else { "s" }
+    );
+
+    let report_path = output_dir.join(REPORT_FILE);
+    if report.notes.is_empty() {
+        println!("  Nothing needs a manual change.");
+    } else {
+        println!(
+            "\n{} item{} to review:",
+            report.notes.len(),
+            if report.notes.len() == 1 { "" } else { "s" }
+        );
+        for note in &report.notes {
+            println!("  {} {}", style("-").yellow(), note);
+        }
+    }
+    println!(
+        "\n  {} {}",
+        style("Report written to").dim(),
+        report_path.display()
+    );
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use console::style;
 use miette::Result;
 
+use diecut::adapter::detect_format;
 use diecut::template::{list_cached, CachedTemplate};
 
 pub fn run() -> Result<()> {
@@ -35,11 +36,16 @@ fn print_entry(entry: &CachedTemplate) {
 
     println!("  {} {}", style("source:").dim(), entry.metadata.url);
     println!("  {}    {}", style("ref:").dim(), git_ref);
+    println!(
+        "  {} {}",
+        style("format:").dim(),
+        detect_format(&entry.path).unwrap_or_else(|| "unrecognized".to_string())
+    );
     println!("  {} {}", style("cached:").dim(), cached_at);
     println!();
 }
 
-fn format_timestamp(unix_ts: &str) -> String {
+pub(crate) fn format_timestamp(unix_ts: &str) -> String {
     let secs: u64 = match unix_ts.parse() {
         Ok(s) => s,
         Err(_) => return unix_ts.to_string(),
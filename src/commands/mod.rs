@@ -1,2 +1,7 @@
+pub mod cache;
+pub mod check;
+pub mod config;
+pub mod convert;
 pub mod list;
 pub mod new;
+pub mod update;
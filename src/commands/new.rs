@@ -1,57 +1,62 @@
+use std::io::IsTerminal;
+
 use console::style;
-use diecut::template::{
-    format_resolved_source, resolve_git_protocol, resolve_source, GitProtocol, ResolveOptions,
-};
-use diecut::GenerateOptions;
+use diecut::answers::load_answers_file;
+use diecut::config::UserConfig;
+use diecut::prompt::parse_data_args;
+use diecut::template::{format_resolved_source, resolve_git_protocol, GitProtocol};
+use diecut::{resolve_template_source, GenerateOptions};
 use miette::Result;
 
 #[allow(clippy::too_many_arguments)]
 pub fn run(
-    template: String,
+    template: Option<String>,
+    git_ref: Option<String>,
+    format: Option<String>,
     output: Option<String>,
+    answers_file: Option<String>,
     data: Vec<String>,
+    data_file: Option<String>,
     defaults: bool,
+    no_input: bool,
     overwrite: bool,
     no_hooks: bool,
     dry_run: bool,
     verbose: bool,
     protocol: Option<GitProtocol>,
+    user_config: &UserConfig,
 ) -> Result<()> {
-    let data_pairs: Vec<(String, String)> = data
-        .into_iter()
-        .filter_map(|kv| {
-            let mut parts = kv.splitn(2, '=');
-            let key = parts.next()?.to_string();
-            let value = parts.next()?.to_string();
-            Some((key, value))
-        })
-        .collect();
+    let data_pairs = parse_data_args(&data)?;
 
-    let resolved_protocol = resolve_git_protocol(protocol)?;
-
-    if dry_run {
-        // Resolve the source first so the URL is visible even if clone fails.
-        let source = resolve_source(
-            &template,
-            ResolveOptions {
-                protocol: resolved_protocol,
-                ..Default::default()
-            },
-        )?;
-        println!("{}", format_resolved_source(&source));
-    }
+    let resolved_protocol = resolve_git_protocol(protocol, user_config.protocol.as_deref())?;
 
     let options = GenerateOptions {
         template,
+        git_ref,
+        format,
         output,
+        answers_file: answers_file.map(Into::into),
         data: data_pairs,
+        data_file: data_file.map(Into::into),
         defaults,
+        no_input: no_input || !std::io::stdin().is_terminal(),
         overwrite,
         no_hooks,
+        dry_run,
         protocol: resolved_protocol,
+        abbreviations: user_config.abbreviations.clone(),
     };
 
     if dry_run {
+        // Resolve the source first so the URL is visible even if clone fails.
+        let saved = options
+            .answers_file
+            .as_deref()
+            .map(load_answers_file)
+            .transpose()?;
+        let source = resolve_template_source(&options, saved.as_ref())?;
+        println!("{}", format_resolved_source(&source));
+
         let plan = diecut::plan_generation(options)?;
 
         let rendered_count = plan.render_plan.files.iter().filter(|f| !f.is_copy).count();
@@ -68,7 +73,7 @@ pub fn run(
             println!(
                 "  {} {}",
                 style(action).green(),
-                file.relative_path.display()
+                plan.redact_secrets(&file.relative_path.display().to_string())
             );
 
             if verbose {
@@ -79,7 +84,7 @@ pub fn run(
                         style(format!("[binary file, {} bytes]", file.content.len())).dim()
                     );
                 } else {
-                    let content = String::from_utf8_lossy(&file.content);
+                    let content = plan.redact_secrets(&String::from_utf8_lossy(&file.content));
                     for line in content.lines() {
                         println!("  {}", line);
                     }
@@ -0,0 +1,79 @@
+use std::io::IsTerminal;
+use std::path::PathBuf;
+
+use console::style;
+use miette::Result;
+
+use diecut::update::{update_project, UpdateOptions, UpdateReport};
+
+pub fn run(
+    path: Option<String>,
+    git_ref: Option<String>,
+    defaults: bool,
+    no_input: bool,
+) -> Result<()> {
+    let project_dir = match path {
+        Some(p) => PathBuf::from(p),
+        None => std::env::current_dir().map_err(|e| diecut::error::DicecutError::Io {
+            context: "getting current directory".into(),
+            source: e,
+        })?,
+    };
+
+    let report = update_project(UpdateOptions {
+        project_dir: project_dir.clone(),
+        git_ref,
+        defaults,
+        no_input: no_input || !std::io::stdin().is_terminal(),
+    })?;
+
+    if report.is_up_to_date() {
+        println!(
+            "{} Already up to date with template commit {}",
+            style("✓").green().bold(),
+            short_sha(&report.old_commit)
+        );
+        return Ok(());
+    }
+
+    print_report(&report);
+
+    println!(
+        "\n{} Updated {} from {} to {}",
+        style("✓").green().bold(),
+        style(project_dir.display()).cyan(),
+        short_sha(&report.old_commit),
+        short_sha(report.new_commit.as_deref().unwrap_or("unknown"))
+    );
+
+    if !report.conflicts.is_empty() || !report.rejected.is_empty() {
+        println!(
+            "  {} resolve conflict markers and review .rej files before committing",
+            style("note:").yellow().bold()
+        );
+    }
+
+    Ok(())
+}
+
+fn print_report(report: &UpdateReport) {
+    print_group("update  ", &report.updated, |s| style(s).green());
+    print_group("remove  ", &report.removed, |s| style(s).red());
+    print_group("keep    ", &report.kept, |s| style(s).dim());
+    print_group("conflict", &report.conflicts, |s| style(s).yellow());
+    print_group("reject  ", &report.rejected, |s| style(s).yellow());
+}
+
+fn print_group(
+    label: &'static str,
+    paths: &[PathBuf],
+    color: impl Fn(&'static str) -> console::StyledObject<&'static str>,
+) {
+    for path in paths {
+        println!("  {} {}", color(label), path.display());
+    }
+}
+
+fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
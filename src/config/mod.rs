@@ -7,7 +7,10 @@ use std::path::Path;
 use crate::error::{DicecutError, Result};
 
 pub use schema::TemplateConfig;
-pub use user::{load_user_config, UserConfig};
+pub use user::{
+    get_user_config_value, list_user_config_values, load_user_config, set_user_config_value,
+    user_config_path, UserConfig,
+};
 
 pub fn load_config(path: &Path) -> Result<TemplateConfig> {
     let config_path = if path.ends_with("diecut.toml") {
@@ -29,6 +32,7 @@ pub fn load_config(path: &Path) -> Result<TemplateConfig> {
         toml::from_str(&content).map_err(|e| DicecutError::ConfigParse { source: e })?;
 
     config.validate()?;
+    config.check_diecut_version(env!("CARGO_PKG_VERSION"))?;
 
     Ok(config)
 }
@@ -4,29 +4,38 @@ use serde::{Deserialize, Serialize};
 use super::variable::{VariableConfig, VariableType};
 use crate::error::{DicecutError, Result};
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct TemplateConfig {
     pub template: TemplateMetadata,
 
     #[serde(default)]
     pub variables: IndexMap<String, VariableConfig>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "FilesConfig::is_empty")]
     pub files: FilesConfig,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "HooksConfig::is_empty")]
     pub hooks: HooksConfig,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "AnswersConfig::is_default")]
     pub answers: AnswersConfig,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct TemplateMetadata {
     pub name: String,
     pub version: Option<String>,
     pub description: Option<String>,
     pub min_diecut_version: Option<String>,
+    pub max_diecut_version: Option<String>,
+
+    /// Shown as a warning whenever the template is used.
+    #[serde(default)]
+    pub deprecated: Option<String>,
+
+    /// Base template (local path or git source) this template layers on top of.
+    #[serde(default)]
+    pub extends: Option<String>,
 
     #[serde(default)]
     pub templates_suffix: Option<String>,
@@ -37,14 +46,32 @@ pub const DEPRECATED_TERA_SUFFIX: &str = ".tera";
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct FilesConfig {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub exclude: Vec<String>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub copy_without_render: Vec<String>,
 
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub conditional: Vec<ConditionalFile>,
+
+    /// Glob patterns for files in `template/` that are loaded as Tera partials
+    /// (for `include`, `import`, and `extends`) instead of being written out.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub partials: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub each: Vec<EachFile>,
+}
+
+impl FilesConfig {
+    fn is_empty(&self) -> bool {
+        self.exclude.is_empty()
+            && self.copy_without_render.is_empty()
+            && self.conditional.is_empty()
+            && self.partials.is_empty()
+            && self.each.is_empty()
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -54,8 +81,35 @@ pub struct ConditionalFile {
     pub when: String,
 }
 
+/// A `[[files.each]]` rule: render matching files once per element of a list.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EachFile {
+    /// Glob over source paths in `template/`. Braces are literal, so the
+    /// pattern can spell out a path like `src/{{ item }}.rs.die`.
+    pub pattern: String,
+    /// The list, multiselect, or group variable to iterate over.
+    pub over: String,
+    /// Name the current element is bound to while rendering.
+    #[serde(rename = "as", default = "default_each_binding")]
+    pub binding: String,
+}
+
+fn default_each_binding() -> String {
+    "item".to_string()
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
 pub struct HooksConfig {
+    /// Shell command to run in the template directory before any variable is
+    /// asked for, e.g. to check that required tools are installed.
+    #[serde(default)]
+    pub pre_prompt: Option<String>,
+
+    /// Shell command to run in the template directory once every answer is
+    /// known, before anything is written. A failure aborts generation.
+    #[serde(default)]
+    pub pre_create: Option<String>,
+
     /// Shell command to run in the output directory after generation.
     #[serde(default)]
     pub post_create: Option<String>,
@@ -63,7 +117,11 @@ pub struct HooksConfig {
 
 impl HooksConfig {
     pub fn has_hooks(&self) -> bool {
-        self.post_create.is_some()
+        self.pre_prompt.is_some() || self.pre_create.is_some() || self.post_create.is_some()
+    }
+
+    fn is_empty(&self) -> bool {
+        !self.has_hooks()
     }
 }
 
@@ -85,26 +143,185 @@ impl Default for AnswersConfig {
     }
 }
 
+impl AnswersConfig {
+    fn is_default(&self) -> bool {
+        self.file == default_answers_file()
+    }
+}
+
 impl TemplateConfig {
     pub fn validate(&self) -> Result<()> {
         for (name, var) in &self.variables {
-            // select/multiselect must have choices
-            if matches!(
-                var.var_type,
-                VariableType::Select | VariableType::Multiselect
-            ) && var.choices.is_none()
+            validate_variable(name, var)?;
+        }
+        // A layer that extends a base may loop over the base's variables, so
+        // its rules are checked once the layers are merged.
+        if self.template.extends.is_none() {
+            self.validate_each_rules()?;
+        }
+
+        Ok(())
+    }
+
+    /// Check every `[[files.each]]` rule against the variables it names.
+    pub fn validate_each_rules(&self) -> Result<()> {
+        for rule in &self.files.each {
+            self.validate_each(rule)?;
+        }
+        Ok(())
+    }
+
+    fn validate_each(&self, rule: &EachFile) -> Result<()> {
+        let invalid = |reason: String| DicecutError::InvalidEachRule {
+            pattern: rule.pattern.clone(),
+            reason,
+        };
+        match self.variables.get(&rule.over) {
+            None => {
+                return Err(invalid(format!(
+                    "'over' names '{}', which is not a variable",
+                    rule.over
+                )))
+            }
+            Some(var)
+                if !matches!(
+                    var.var_type,
+                    VariableType::List | VariableType::Multiselect | VariableType::Group
+                ) =>
             {
-                return Err(DicecutError::ConfigInvalidVariable {
-                    name: name.clone(),
-                    reason: "select/multiselect variables must have 'choices' defined".into(),
-                });
+                return Err(invalid(format!(
+                    "'{}' is a {} variable; 'over' needs a list, multiselect, or group",
+                    rule.over,
+                    var.var_type.as_str()
+                )))
             }
+            Some(_) => {}
+        }
+        let is_identifier = rule
+            .binding
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && rule
+                .binding
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_identifier {
+            return Err(invalid(format!(
+                "'as' must be a plain name, not '{}'",
+                rule.binding
+            )));
+        }
+        if self.variables.contains_key(&rule.binding) {
+            return Err(invalid(format!(
+                "'as = \"{}\"' would hide the variable of the same name",
+                rule.binding
+            )));
+        }
+        Ok(())
+    }
+
+    /// Layer this config on top of a base template's config.
+    ///
+    /// Metadata comes from `self` (falling back to the base's `templates_suffix`).
+    /// Variables keep the base's order; a variable redefined here replaces the
+    /// base definition in place and new variables are appended. File rules are
+    /// combined, and a hook defined here replaces the base's.
+    pub fn inherit(self, base: TemplateConfig) -> TemplateConfig {
+        let mut template = self.template;
+        if template.templates_suffix.is_none() {
+            template.templates_suffix = base.template.templates_suffix;
+        }
+
+        let mut variables = base.variables;
+        for (name, var) in self.variables {
+            variables.insert(name, var);
+        }
 
-            // computed variables shouldn't have a prompt
-            if var.computed.is_some() && var.prompt.is_some() {
-                return Err(DicecutError::ConfigInvalidVariable {
-                    name: name.clone(),
-                    reason: "computed variables should not have a 'prompt' field".into(),
+        let mut files = base.files;
+        for pattern in self.files.exclude {
+            if !files.exclude.contains(&pattern) {
+                files.exclude.push(pattern);
+            }
+        }
+        for pattern in self.files.copy_without_render {
+            if !files.copy_without_render.contains(&pattern) {
+                files.copy_without_render.push(pattern);
+            }
+        }
+        for pattern in self.files.partials {
+            if !files.partials.contains(&pattern) {
+                files.partials.push(pattern);
+            }
+        }
+        files.conditional.extend(self.files.conditional);
+        files.each.extend(self.files.each);
+
+        let hooks = HooksConfig {
+            pre_prompt: self.hooks.pre_prompt.or(base.hooks.pre_prompt),
+            pre_create: self.hooks.pre_create.or(base.hooks.pre_create),
+            post_create: self.hooks.post_create.or(base.hooks.post_create),
+        };
+
+        TemplateConfig {
+            template,
+            variables,
+            files,
+            hooks,
+            answers: self.answers,
+        }
+    }
+
+    /// Check `min_diecut_version`/`max_diecut_version` against the running diecut version.
+    pub fn check_diecut_version(&self, current: &str) -> Result<()> {
+        let (current_version, _) = parse_version("diecut", current)?;
+
+        let min = self
+            .template
+            .min_diecut_version
+            .as_deref()
+            .map(|v| parse_version("min_diecut_version", v))
+            .transpose()?;
+        let max = self
+            .template
+            .max_diecut_version
+            .as_deref()
+            .map(|v| parse_version("max_diecut_version", v))
+            .transpose()?;
+
+        if let Some((min, _)) = min.filter(|(min, _)| current_version < *min) {
+            return Err(DicecutError::IncompatibleDiecutVersion {
+                requirement: format!(">= {min}"),
+                current: current.to_string(),
+                install_hint: format!(
+                    "Install diecut {min} or newer: cargo install diecut --version '>={min}'"
+                ),
+            });
+        }
+        if let Some((max, parts)) = max {
+            // A partial maximum covers the whole series: `0.4` allows any 0.4.x.
+            let (op, bound) = match parts {
+                1 => ("<", semver::Version::new(max.major + 1, 0, 0)),
+                2 => ("<", semver::Version::new(max.major, max.minor + 1, 0)),
+                _ => ("<=", max),
+            };
+            let release = semver::Version::new(
+                current_version.major,
+                current_version.minor,
+                current_version.patch,
+            );
+            let too_new = if op == "<" {
+                release >= bound
+            } else {
+                current_version > bound
+            };
+            if too_new {
+                return Err(DicecutError::IncompatibleDiecutVersion {
+                    requirement: format!("{op} {bound}"),
+                    current: current.to_string(),
+                    install_hint: format!(
+                        "Install an older diecut: cargo install diecut --version '{op}{bound}'"
+                    ),
                 });
             }
         }
@@ -112,3 +329,190 @@ impl TemplateConfig {
         Ok(())
     }
 }
+
+/// Parse a version, accepting a leading `v` and missing minor/patch parts (`0.4` → `0.4.0`).
+///
+/// Also returns how many of the major/minor/patch parts were written out.
+fn parse_version(field: &'static str, value: &str) -> Result<(semver::Version, usize)> {
+    let trimmed = value.trim().trim_start_matches('v');
+    let parts = trimmed
+        .split(['-', '+'])
+        .next()
+        .unwrap_or("")
+        .matches('.')
+        .count()
+        + 1;
+    let padded = match parts {
+        1 => format!("{trimmed}.0.0"),
+        2 => format!("{trimmed}.0"),
+        _ => trimmed.to_string(),
+    };
+    semver::Version::parse(&padded)
+        .map(|version| (version, parts))
+        .map_err(|_| DicecutError::InvalidDiecutVersion {
+            field,
+            value: value.to_string(),
+        })
+}
+
+/// Check one variable definition, including the `fields` of a table or group.
+fn validate_variable(name: &str, var: &VariableConfig) -> Result<()> {
+    let invalid = |reason: String| DicecutError::ConfigInvalidVariable {
+        name: name.to_string(),
+        reason,
+    };
+
+    // select/multiselect must have choices
+    if matches!(
+        var.var_type,
+        VariableType::Select | VariableType::Multiselect
+    ) && var.choices.is_none()
+        && var.choices_from.is_none()
+    {
+        return Err(invalid(
+            "select/multiselect variables must have 'choices' or 'choices_from' defined".into(),
+        ));
+    }
+
+    check_choice_config(var).map_err(invalid)?;
+    if let Some(validator) = &var.validator {
+        parse_template("validator", validator).map_err(invalid)?;
+    }
+
+    // computed variables shouldn't have a prompt
+    if var.computed.is_some() && var.prompt.is_some() {
+        return Err(invalid(
+            "computed variables should not have a 'prompt' field".into(),
+        ));
+    }
+
+    if var.confirm && !var.secret {
+        return Err(invalid("'confirm' only applies to secret variables".into()));
+    }
+
+    check_constraint_config(var).map_err(invalid)?;
+
+    let structured = matches!(var.var_type, VariableType::Table | VariableType::Group);
+    match &var.fields {
+        None if structured => {
+            return Err(invalid(
+                "table/group variables must have 'fields' defined".into(),
+            ))
+        }
+        Some(_) if !structured => {
+            return Err(invalid(
+                "'fields' only applies to table and group variables".into(),
+            ))
+        }
+        Some(fields) => {
+            for (field_name, field) in fields {
+                if matches!(field.var_type, VariableType::Table | VariableType::Group) {
+                    return Err(invalid(format!(
+                        "field '{field_name}' can't be a table or group"
+                    )));
+                }
+                validate_variable(&format!("{name}.{field_name}"), field)?;
+            }
+        }
+        None => {}
+    }
+
+    Ok(())
+}
+
+/// Check that a Tera template parses, naming `what` in the message.
+fn parse_template(what: &str, template: &str) -> std::result::Result<(), String> {
+    tera::Tera::default()
+        .add_raw_template(what, template)
+        .map(|_| ())
+        .map_err(|e| match std::error::Error::source(&e) {
+            Some(cause) => format!("invalid {what}: {cause}"),
+            None => format!("invalid {what}: {e}"),
+        })
+}
+
+/// Check that `choices` and `choices_from` are not mixed, choice values are
+/// unique, and every expression parses.
+fn check_choice_config(var: &VariableConfig) -> std::result::Result<(), String> {
+    if let Some(template) = &var.choices_from {
+        if !matches!(
+            var.var_type,
+            VariableType::Select | VariableType::Multiselect
+        ) {
+            return Err("'choices_from' only applies to select and multiselect variables".into());
+        }
+        if var.choices.is_some() {
+            return Err("use either 'choices' or 'choices_from', not both".into());
+        }
+        parse_template("choices_from", template)?;
+    }
+
+    let choices = var.choices.as_deref().unwrap_or_default();
+    for (i, choice) in choices.iter().enumerate() {
+        if choices[..i].iter().any(|c| c.value == choice.value) {
+            return Err(format!("choice '{}' is listed twice", choice.value));
+        }
+        if let Some(when) = &choice.when {
+            parse_template(
+                &format!("`when` of choice '{}'", choice.value),
+                &format!("{{% if {when} %}}{{% endif %}}"),
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Check that constraint keys suit the variable's type, bounds are ordered,
+/// and a literal default satisfies them.
+fn check_constraint_config(var: &VariableConfig) -> std::result::Result<(), String> {
+    let numeric = matches!(var.var_type, VariableType::Int | VariableType::Float);
+    let string = var.var_type == VariableType::String;
+    let repeated = matches!(
+        var.var_type,
+        VariableType::Multiselect | VariableType::List | VariableType::Group
+    );
+    let misplaced = [
+        ("min", var.min.is_some() && !numeric, "int and float"),
+        ("max", var.max.is_some() && !numeric, "int and float"),
+        ("min_length", var.min_length.is_some() && !string, "string"),
+        ("max_length", var.max_length.is_some() && !string, "string"),
+        (
+            "min_items",
+            var.min_items.is_some() && !repeated,
+            "multiselect, list, and group",
+        ),
+        (
+            "max_items",
+            var.max_items.is_some() && !repeated,
+            "multiselect, list, and group",
+        ),
+    ];
+    if let Some((key, _, applies_to)) = misplaced.iter().find(|(_, bad, _)| *bad) {
+        return Err(format!("'{key}' only applies to {applies_to} variables"));
+    }
+
+    let inverted =
+        |low: Option<f64>, high: Option<f64>| matches!((low, high), (Some(l), Some(h)) if l > h);
+    if inverted(var.min, var.max) {
+        return Err("'min' is greater than 'max'".into());
+    }
+    if inverted(
+        var.min_length.map(|n| n as f64),
+        var.max_length.map(|n| n as f64),
+    ) {
+        return Err("'min_length' is greater than 'max_length'".into());
+    }
+    if inverted(
+        var.min_items.map(|n| n as f64),
+        var.max_items.map(|n| n as f64),
+    ) {
+        return Err("'min_items' is greater than 'max_items'".into());
+    }
+
+    match &var.default {
+        Some(default) if var.templated_default().is_none() => var
+            .check_constraints(&crate::prompt::engine::toml_to_tera_value(default))
+            .map_err(|reason| format!("default {default} {reason}")),
+        _ => Ok(()),
+    }
+}
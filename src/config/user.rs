@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::io::Write;
 use std::path::PathBuf;
 
 use serde::{Deserialize, Serialize};
@@ -7,40 +8,214 @@ use crate::error::{DicecutError, Result};
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct UserConfig {
+    /// Protocol for built-in shortcodes (`ssh` or `https`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub protocol: Option<String>,
+
     /// Prefix → URL template with `{}` placeholder (e.g. `"https://git.company.com/{}.git"`).
     #[serde(default)]
     pub abbreviations: HashMap<String, String>,
 }
 
-fn config_path() -> Option<PathBuf> {
-    dirs::config_dir().map(|d| d.join("diecut").join("config.toml"))
+/// Path of the user config file.
+///
+/// Checks `DIECUT_CONFIG_DIR` env var first. Falls back to
+/// `~/.config/diecut/config.toml` on Linux (platform config dir elsewhere).
+pub fn user_config_path() -> Result<PathBuf> {
+    if let Ok(dir) = std::env::var("DIECUT_CONFIG_DIR") {
+        return Ok(PathBuf::from(dir).join("config.toml"));
+    }
+    dirs::config_dir()
+        .map(|d| d.join("diecut").join("config.toml"))
+        .ok_or_else(|| DicecutError::Io {
+            context: "unable to determine config directory: set DIECUT_CONFIG_DIR or ensure a home directory exists".into(),
+            source: std::io::Error::new(std::io::ErrorKind::NotFound, "no config directory available"),
+        })
 }
 
 /// Returns `Ok(None)` if the config file does not exist.
 pub fn load_user_config() -> Result<Option<UserConfig>> {
-    let path = match config_path() {
-        Some(p) => p,
-        None => return Ok(None),
+    let Some(table) = read_config_table()? else {
+        return Ok(None);
     };
+    let path = user_config_path()?;
+    let config = table
+        .try_into()
+        .map_err(|e| DicecutError::UserConfigParse { path, source: e })?;
+    Ok(Some(config))
+}
 
-    if !path.exists() {
+/// Look up a single setting by dotted key (`protocol`, `abbreviations.company`).
+pub fn get_user_config_value(key: &str) -> Result<Option<String>> {
+    validate_key(key)?;
+    let Some(table) = read_config_table()? else {
         return Ok(None);
+    };
+    let mut current = &toml::Value::Table(table);
+    for part in key.split('.') {
+        match current.get(part) {
+            Some(value) => current = value,
+            None => return Ok(None),
+        }
+    }
+    Ok(Some(display_value(current)))
+}
+
+/// Set a single setting by dotted key, keeping every other setting in the file.
+///
+/// The file is edited in place, so comments and formatting survive. The value
+/// is validated before anything is written, and the file is replaced
+/// atomically so a failed write never leaves a truncated config behind.
+pub fn set_user_config_value(key: &str, value: &str) -> Result<PathBuf> {
+    validate_key(key)?;
+    validate_value(key, value)?;
+
+    let path = user_config_path()?;
+    // Parse with `toml` first so a malformed file reports the usual error.
+    read_config_table()?;
+    let content = read_config_content(&path)?.unwrap_or_default();
+    let mut doc =
+        content
+            .parse::<toml_edit::DocumentMut>()
+            .map_err(|e| DicecutError::UserConfigEdit {
+                path: path.clone(),
+                source: e,
+            })?;
+
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let last = parts.pop().expect("split yields at least one part");
+    let mut current: &mut dyn toml_edit::TableLike = doc.as_table_mut();
+    for part in parts {
+        let entry = current.entry(part).or_insert_with(toml_edit::table);
+        current = entry
+            .as_table_like_mut()
+            .ok_or_else(|| DicecutError::InvalidConfigValue {
+                key: key.to_string(),
+                reason: format!("'{part}' is not a table in {}", path.display()),
+            })?;
     }
+    current.insert(last, toml_edit::value(value));
 
-    let content = std::fs::read_to_string(&path).map_err(|e| DicecutError::Io {
-        context: format!("reading user config {}", path.display()),
+    // Make sure the result still loads before replacing the file.
+    let content = doc.to_string();
+    let _: UserConfig = toml::from_str(&content).map_err(|e| DicecutError::UserConfigParse {
+        path: path.clone(),
         source: e,
     })?;
 
-    let config: UserConfig =
-        toml::from_str(&content).map_err(|e| DicecutError::ConfigParse { source: e })?;
+    write_config_file(&path, &content)?;
+    Ok(path)
+}
 
-    Ok(Some(config))
+/// Every setting in the config file as `(dotted key, value)` pairs, sorted by key.
+pub fn list_user_config_values() -> Result<Vec<(String, String)>> {
+    let Some(table) = read_config_table()? else {
+        return Ok(Vec::new());
+    };
+    let mut values = Vec::new();
+    flatten_table("", &table, &mut values);
+    values.sort();
+    Ok(values)
+}
+
+fn validate_key(key: &str) -> Result<()> {
+    let valid = match key.split_once('.') {
+        None => key == "protocol",
+        Some(("abbreviations", prefix)) => !prefix.is_empty() && !prefix.contains(['.', ':', '/']),
+        Some(_) => false,
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(DicecutError::UnknownConfigKey {
+            key: key.to_string(),
+        })
+    }
+}
+
+fn validate_value(key: &str, value: &str) -> Result<()> {
+    let reason = if key == "protocol" {
+        (value != "ssh" && value != "https").then_some("expected 'ssh' or 'https'")
+    } else {
+        (!value.contains("{}")).then_some("URL template must contain a {} placeholder")
+    };
+    match reason {
+        Some(reason) => Err(DicecutError::InvalidConfigValue {
+            key: key.to_string(),
+            reason: reason.to_string(),
+        }),
+        None => Ok(()),
+    }
+}
+
+fn read_config_table() -> Result<Option<toml::Table>> {
+    let path = user_config_path()?;
+    let Some(content) = read_config_content(&path)? else {
+        return Ok(None);
+    };
+
+    let table = content
+        .parse::<toml::Table>()
+        .map_err(|e| DicecutError::UserConfigParse { path, source: e })?;
+    Ok(Some(table))
+}
+
+fn read_config_content(path: &std::path::Path) -> Result<Option<String>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    std::fs::read_to_string(path)
+        .map(Some)
+        .map_err(|e| DicecutError::Io {
+            context: format!("reading user config {}", path.display()),
+            source: e,
+        })
+}
+
+fn write_config_file(path: &std::path::Path, content: &str) -> Result<()> {
+    let dir = path.parent().unwrap_or_else(|| std::path::Path::new("."));
+    let io_err = |context: String| move |e: std::io::Error| DicecutError::Io { context, source: e };
+
+    std::fs::create_dir_all(dir).map_err(io_err(format!("creating {}", dir.display())))?;
+
+    let mut tmp = tempfile::NamedTempFile::new_in(dir).map_err(io_err(format!(
+        "creating temporary file in {}",
+        dir.display()
+    )))?;
+    tmp.write_all(content.as_bytes())
+        .map_err(io_err(format!("writing {}", tmp.path().display())))?;
+    tmp.persist(path)
+        .map_err(|e| io_err(format!("writing {}", path.display()))(e.error))?;
+    Ok(())
+}
+
+fn flatten_table(prefix: &str, table: &toml::Table, out: &mut Vec<(String, String)>) {
+    for (key, value) in table {
+        let full_key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        match value {
+            toml::Value::Table(inner) => flatten_table(&full_key, inner, out),
+            other => out.push((full_key, display_value(other))),
+        }
+    }
+}
+
+fn display_value(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rstest::rstest;
+    use serial_test::serial;
 
     #[test]
     fn parse_valid_user_config() {
@@ -80,8 +255,124 @@ internal = "https://internal.example.com/repos/{}"
     }
 
     #[test]
+    #[serial]
     fn load_user_config_returns_none_when_no_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::env::set_var("DIECUT_CONFIG_DIR", dir.path());
         let result = load_user_config();
-        assert!(result.is_ok());
+        std::env::remove_var("DIECUT_CONFIG_DIR");
+        assert!(result.unwrap().is_none());
+    }
+
+    #[test]
+    fn parse_protocol_setting() {
+        let config: UserConfig = toml::from_str(r#"protocol = "https""#).unwrap();
+        assert_eq!(config.protocol.as_deref(), Some("https"));
+    }
+
+    #[test]
+    #[serial]
+    fn set_get_and_list_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        std::env::set_var("DIECUT_CONFIG_DIR", dir.path().join("nested"));
+
+        set_user_config_value("protocol", "https").unwrap();
+        set_user_config_value("abbreviations.company", "https://git.company.com/{}.git").unwrap();
+        let protocol = get_user_config_value("protocol").unwrap();
+        let missing = get_user_config_value("abbreviations.other").unwrap();
+        let listed = list_user_config_values().unwrap();
+        let loaded = load_user_config().unwrap().unwrap();
+
+        std::env::remove_var("DIECUT_CONFIG_DIR");
+
+        assert_eq!(protocol.as_deref(), Some("https"));
+        assert!(missing.is_none());
+        assert_eq!(
+            listed,
+            vec![
+                (
+                    "abbreviations.company".to_string(),
+                    "https://git.company.com/{}.git".to_string()
+                ),
+                ("protocol".to_string(), "https".to_string()),
+            ]
+        );
+        assert_eq!(
+            loaded.abbreviations["company"],
+            "https://git.company.com/{}.git"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn set_keeps_unrelated_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("config.toml"),
+            "[abbreviations]\nwork = \"https://work.example.com/{}.git\"\n",
+        )
+        .unwrap();
+        std::env::set_var("DIECUT_CONFIG_DIR", dir.path());
+
+        set_user_config_value("protocol", "ssh").unwrap();
+        let loaded = load_user_config().unwrap().unwrap();
+
+        std::env::remove_var("DIECUT_CONFIG_DIR");
+
+        assert_eq!(loaded.protocol.as_deref(), Some("ssh"));
+        assert_eq!(
+            loaded.abbreviations["work"],
+            "https://work.example.com/{}.git"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn set_preserves_comments_and_formatting() {
+        let dir = tempfile::tempdir().unwrap();
+        let original = "# Personal diecut settings\nprotocol = \"https\" # for CI\n\n[abbreviations]\n# Work repos\nwork = \"https://work.example.com/{}.git\"\n";
+        std::fs::write(dir.path().join("config.toml"), original).unwrap();
+        std::env::set_var("DIECUT_CONFIG_DIR", dir.path());
+
+        set_user_config_value("abbreviations.home", "https://home.example.com/{}.git").unwrap();
+        let written = std::fs::read_to_string(dir.path().join("config.toml")).unwrap();
+
+        std::env::remove_var("DIECUT_CONFIG_DIR");
+
+        assert_eq!(
+            written,
+            format!("{original}home = \"https://home.example.com/{{}}.git\"\n")
+        );
+    }
+
+    #[rstest]
+    #[case("protocol", "tcp")]
+    #[case("abbreviations.company", "https://git.company.com/repo.git")]
+    #[serial]
+    fn set_rejects_invalid_values(#[case] key: &str, #[case] value: &str) {
+        let dir = tempfile::tempdir().unwrap();
+        std::env::set_var("DIECUT_CONFIG_DIR", dir.path());
+        let result = set_user_config_value(key, value);
+        let written = dir.path().join("config.toml").exists();
+        std::env::remove_var("DIECUT_CONFIG_DIR");
+
+        assert!(matches!(
+            result,
+            Err(DicecutError::InvalidConfigValue { .. })
+        ));
+        assert!(!written);
+    }
+
+    #[rstest]
+    #[case("protocl")]
+    #[case("abbreviations")]
+    #[case("abbreviations.")]
+    #[case("abbreviations.gh:")]
+    #[case("cache.dir")]
+    fn unknown_keys_are_rejected(#[case] key: &str) {
+        assert!(matches!(
+            validate_key(key),
+            Err(DicecutError::UnknownConfigKey { .. })
+        ));
     }
 }
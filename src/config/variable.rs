@@ -1,3 +1,6 @@
+use std::fmt;
+
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Default)]
@@ -10,6 +13,128 @@ pub enum VariableType {
     Float,
     Select,
     Multiselect,
+    /// Free-form string entries, asked for one at a time.
+    List,
+    /// An object built from the `fields` sub-variables.
+    Table,
+    /// Repeated `fields` entries, asked for until the user stops.
+    Group,
+}
+
+impl VariableType {
+    /// The name used for this type in diecut.toml.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VariableType::String => "string",
+            VariableType::Bool => "bool",
+            VariableType::Int => "int",
+            VariableType::Float => "float",
+            VariableType::Select => "select",
+            VariableType::Multiselect => "multiselect",
+            VariableType::List => "list",
+            VariableType::Table => "table",
+            VariableType::Group => "group",
+        }
+    }
+}
+
+/// One option of a `select` or `multiselect`.
+///
+/// Written either as a plain string or as a table with a `value` and an
+/// optional `label`, `help`, and `when` (the choice is hidden when false).
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(from = "ChoiceRepr", into = "ChoiceRepr")]
+pub struct Choice {
+    pub value: String,
+    pub label: Option<String>,
+    pub help: Option<String>,
+    pub when: Option<String>,
+}
+
+/// Text shown at the prompt: the label (or value), then the help text.
+impl fmt::Display for Choice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.label.as_deref().unwrap_or(&self.value))?;
+        if let Some(help) = &self.help {
+            write!(f, " - {help}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for Choice {
+    fn from(value: &str) -> Self {
+        Choice::from(value.to_string())
+    }
+}
+
+impl From<String> for Choice {
+    fn from(value: String) -> Self {
+        Choice {
+            value,
+            label: None,
+            help: None,
+            when: None,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum ChoiceRepr {
+    Value(String),
+    Table {
+        value: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        label: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        help: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        when: Option<String>,
+    },
+}
+
+impl From<ChoiceRepr> for Choice {
+    fn from(repr: ChoiceRepr) -> Self {
+        match repr {
+            ChoiceRepr::Value(value) => Choice::from(value),
+            ChoiceRepr::Table {
+                value,
+                label,
+                help,
+                when,
+            } => Choice {
+                value,
+                label,
+                help,
+                when,
+            },
+        }
+    }
+}
+
+impl From<Choice> for ChoiceRepr {
+    fn from(choice: Choice) -> Self {
+        match choice {
+            Choice {
+                value,
+                label: None,
+                help: None,
+                when: None,
+            } => ChoiceRepr::Value(value),
+            Choice {
+                value,
+                label,
+                help,
+                when,
+            } => ChoiceRepr::Table {
+                value,
+                label,
+                help,
+                when,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize, Default)]
@@ -18,19 +143,102 @@ pub struct VariableConfig {
     pub var_type: VariableType,
     pub prompt: Option<String>,
     pub default: Option<toml::Value>,
-    pub choices: Option<Vec<String>>,
+    pub choices: Option<Vec<Choice>>,
+    /// Tera expression evaluated against earlier answers to build the choices:
+    /// a list of strings or of tables with `value`, `label`, and `help`.
+    pub choices_from: Option<String>,
     pub validation: Option<String>,
     pub validation_message: Option<String>,
+    /// Tera template rendered with the answers so far and this value; any
+    /// non-blank output rejects the value and is shown as the message.
+    pub validator: Option<String>,
     /// If false, this variable is skipped during prompting.
     pub when: Option<String>,
     /// Tera expression — computed variables are never prompted.
     pub computed: Option<String>,
-    #[serde(default)]
+    /// Masked at the prompt, redacted from dry runs, and never saved to the answers file.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub secret: bool,
+    /// Ask for a secret twice and require both entries to match.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub confirm: bool,
+    /// Smallest accepted value for `int` and `float` variables.
+    pub min: Option<f64>,
+    /// Largest accepted value for `int` and `float` variables.
+    pub max: Option<f64>,
+    /// Fewest characters accepted for `string` variables.
+    pub min_length: Option<usize>,
+    /// Most characters accepted for `string` variables.
+    pub max_length: Option<usize>,
+    /// Fewest entries accepted for `multiselect`, `list`, and `group` variables.
+    pub min_items: Option<usize>,
+    /// Most entries accepted for `multiselect`, `list`, and `group` variables.
+    pub max_items: Option<usize>,
+    /// Sub-variables of a `table`, or of each entry of a `group`.
+    pub fields: Option<IndexMap<String, VariableConfig>>,
 }
 
 impl VariableConfig {
     pub fn is_prompted(&self) -> bool {
         self.computed.is_none()
     }
+
+    /// Whether `value` is one of the declared choices.
+    pub fn has_choice(&self, value: &str) -> bool {
+        self.choices
+            .iter()
+            .flatten()
+            .any(|choice| choice.value == value)
+    }
+
+    /// The default as a Tera template, when it refers to other answers.
+    pub fn templated_default(&self) -> Option<&str> {
+        match &self.default {
+            Some(toml::Value::String(s)) if s.contains("{{") || s.contains("{%") => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Check a value against `min`/`max`, `min_length`/`max_length` and
+    /// `min_items`/`max_items`. Values of the wrong shape are left to type checks.
+    pub fn check_constraints(&self, value: &serde_json::Value) -> Result<(), String> {
+        match value {
+            serde_json::Value::Number(n) => match n.as_f64() {
+                Some(n) => self.check_range(n),
+                None => Ok(()),
+            },
+            serde_json::Value::String(s) => self.check_length(s),
+            serde_json::Value::Array(items) => self.check_items(items.len()),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_range(&self, n: f64) -> Result<(), String> {
+        match (self.min, self.max) {
+            (Some(min), _) if n < min => Err(format!("must be at least {min}")),
+            (_, Some(max)) if n > max => Err(format!("must be at most {max}")),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_length(&self, s: &str) -> Result<(), String> {
+        let len = s.chars().count();
+        match (self.min_length, self.max_length) {
+            (Some(min), _) if len < min => Err(format!("must be at least {min} characters")),
+            (_, Some(max)) if len > max => Err(format!("must be at most {max} characters")),
+            _ => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_items(&self, count: usize) -> Result<(), String> {
+        let verb = match self.var_type {
+            VariableType::Multiselect => "select",
+            _ => "have",
+        };
+        match (self.min_items, self.max_items) {
+            (Some(min), _) if count < min => Err(format!("must {verb} at least {min} items")),
+            (_, Some(max)) if count > max => Err(format!("must {verb} at most {max} items")),
+            _ => Ok(()),
+        }
+    }
 }
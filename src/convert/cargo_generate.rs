@@ -0,0 +1,496 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use regex_lite::Regex;
+use serde::Deserialize;
+use walkdir::WalkDir;
+
+use super::{template_tags, Source};
+use crate::adapter::{template_name, ResolvedTemplate};
+use crate::config::schema::{ConditionalFile, TemplateConfig, TemplateMetadata};
+use crate::config::variable::{Choice, VariableConfig, VariableType};
+use crate::error::{DicecutError, Result};
+use crate::render::file::is_binary_file;
+
+pub const CONFIG_FILES: &[&str] = &["cargo-generate.toml", ".cargo-generate.toml"];
+
+pub const FORMAT: &str = "cargo-generate";
+
+/// Suffix cargo-generate strips from file names after rendering them.
+const LIQUID_SUFFIX: &str = ".liquid";
+
+/// Lists files cargo-generate never copies, one pattern per line.
+const GENIGNORE: &str = ".genignore";
+
+/// Liquid spellings with a different name in Tera: filters first, then tags.
+const LIQUID_FILTERS: &[(&str, &str)] = &[
+    ("upcase", "upper"),
+    ("downcase", "lower"),
+    ("strip", "trim"),
+    ("lstrip", "trim_start"),
+    ("rstrip", "trim_end"),
+    ("size", "length"),
+];
+const LIQUID_TAGS: &[(&str, &str)] = &[("elsif", "elif"), ("assign", "set")];
+
+/// Built-in placeholders with no diecut equivalent.
+const UNSUPPORTED_BUILTINS: &[&str] = &["os-arch", "is_init", "within_cargo_project"];
+
+#[derive(Debug, Default, Deserialize)]
+struct CargoGenerateConfig {
+    #[serde(default)]
+    template: TemplateSection,
+    #[serde(default)]
+    placeholders: IndexMap<String, Placeholder>,
+    #[serde(default)]
+    conditional: IndexMap<String, Conditional>,
+    #[serde(flatten)]
+    other: BTreeMap<String, toml::Value>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TemplateSection {
+    /// Only these files are rendered; the rest are copied as-is.
+    include: Option<Vec<String>>,
+    /// Copied without rendering.
+    #[serde(default)]
+    exclude: Vec<String>,
+    /// Not copied at all.
+    #[serde(default)]
+    ignore: Vec<String>,
+    #[serde(flatten)]
+    other: BTreeMap<String, toml::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Placeholder {
+    #[serde(rename = "type")]
+    kind: String,
+    prompt: Option<String>,
+    choices: Option<Vec<String>>,
+    default: Option<toml::Value>,
+    regex: Option<String>,
+}
+
+/// A `[conditional.'<expression>']` table, applied when the expression is true.
+#[derive(Debug, Default, Deserialize)]
+struct Conditional {
+    #[serde(default)]
+    ignore: Vec<String>,
+    #[serde(default)]
+    placeholders: IndexMap<String, Placeholder>,
+}
+
+pub fn config_file(template_dir: &Path) -> Option<PathBuf> {
+    CONFIG_FILES
+        .iter()
+        .map(|name| template_dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// Read a cargo-generate template for conversion.
+///
+/// Liquid is close enough to Tera that most files convert as they are; the
+/// differences diecut knows how to fix (hyphenated placeholder names and a
+/// few renamed filters and tags) are returned as rewrites. A template
+/// without a `cargo-generate.toml` is read with only the built-in placeholders.
+pub(super) fn read(template_dir: &Path) -> Result<Source> {
+    let config_path =
+        config_file(template_dir).unwrap_or_else(|| template_dir.join(CONFIG_FILES[0]));
+    let invalid = |reason: String| DicecutError::ForeignTemplate {
+        format: "cargo-generate",
+        path: config_path.clone(),
+        reason,
+    };
+
+    let parsed: CargoGenerateConfig = if config_path.is_file() {
+        let content = std::fs::read_to_string(&config_path).map_err(|e| DicecutError::Io {
+            context: format!("reading {}", config_path.display()),
+            source: e,
+        })?;
+        toml::from_str(&content).map_err(|e| invalid(e.message().to_string()))?
+    } else {
+        CargoGenerateConfig::default()
+    };
+
+    let mut warnings = Vec::new();
+    for key in parsed.other.keys() {
+        warnings.push(match key.as_str() {
+            "hooks" => "cargo-generate hooks are Rhai scripts and were not converted; \
+                        port them to [hooks] in diecut.toml"
+                .to_string(),
+            _ => format!("cargo-generate section '[{key}]' is not supported and was ignored"),
+        });
+    }
+    for key in parsed.template.other.keys() {
+        if key != "cargo_generate_version" && key != "vcs" {
+            warnings.push(format!(
+                "cargo-generate option 'template.{key}' is not supported and was ignored"
+            ));
+        }
+    }
+
+    let mut config = TemplateConfig {
+        template: TemplateMetadata {
+            name: template_name(template_dir, FORMAT),
+            templates_suffix: Some(String::new()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let expressions: Vec<&str> = parsed.conditional.keys().map(String::as_str).collect();
+    let mentioned = mentioned_names(template_dir, &expressions);
+    let mut renames: Vec<(String, String)> = Vec::new();
+
+    config.variables.insert(
+        "project_name".into(),
+        VariableConfig {
+            prompt: Some("Project name".into()),
+            ..Default::default()
+        },
+    );
+    config.variables.insert(
+        "crate_name".into(),
+        VariableConfig {
+            computed: Some(r#"{{ project_name | slugify | replace(from="-", to="_") }}"#.into()),
+            ..Default::default()
+        },
+    );
+    renames.push(("project-name".into(), "project_name".into()));
+    if mentioned.contains("crate_type") {
+        config.variables.insert(
+            "crate_type".into(),
+            VariableConfig {
+                var_type: VariableType::Select,
+                prompt: Some("Crate type".into()),
+                choices: Some(vec![Choice::from("bin"), Choice::from("lib")]),
+                default: Some(toml::Value::String("bin".into())),
+                ..Default::default()
+            },
+        );
+    }
+    for (name, prompt) in [("authors", "Authors"), ("username", "Username")] {
+        if mentioned.contains(name) {
+            config.variables.insert(
+                name.into(),
+                VariableConfig {
+                    prompt: Some(prompt.into()),
+                    default: Some(toml::Value::String(String::new())),
+                    ..Default::default()
+                },
+            );
+        }
+    }
+    for name in UNSUPPORTED_BUILTINS {
+        if mentioned.contains(*name) {
+            warnings.push(format!(
+                "cargo-generate's built-in placeholder '{name}' has no diecut equivalent"
+            ));
+        }
+    }
+
+    for (name, placeholder) in parsed.placeholders {
+        let var = placeholder_variable(&name, placeholder).map_err(&invalid)?;
+        insert_placeholder(&mut config, &mut renames, name, var);
+    }
+
+    let mut conditional_vars = Vec::new();
+    let mut conditional_files = Vec::new();
+    for (expr, conditional) in parsed.conditional {
+        for (name, placeholder) in conditional.placeholders {
+            let var = placeholder_variable(&name, placeholder).map_err(&invalid)?;
+            let name = insert_placeholder(&mut config, &mut renames, name, var);
+            conditional_vars.push((name, expr.clone()));
+        }
+        for pattern in conditional.ignore {
+            conditional_files.push((pattern, expr.clone()));
+        }
+    }
+    // Expressions can name placeholders declared after them, so they are
+    // rewritten once every rename is known.
+    let rewrites = rewrites(&renames);
+    for (name, expr) in conditional_vars {
+        if let Some(var) = config.variables.get_mut(&name) {
+            var.when = Some(apply(&rewrites, &expr));
+        }
+    }
+    // cargo-generate drops the files when the expression holds, diecut keeps
+    // them when `when` does. Tera has no grouping parentheses for logic, so
+    // only expressions without `and`/`or` are negated automatically.
+    let combined = Regex::new(r"\b(and|or)\b").expect("valid regex");
+    for (pattern, expr) in conditional_files {
+        if combined.is_match(&expr) {
+            warnings.push(format!(
+                "[conditional.'{expr}'] ignore = '{pattern}' was not converted; \
+                 add a [[files.conditional]] rule with the negated expression"
+            ));
+            continue;
+        }
+        config.files.conditional.push(ConditionalFile {
+            pattern,
+            when: format!("not {}", apply(&rewrites, &expr)),
+        });
+    }
+
+    let mut ignore: Vec<String> = CONFIG_FILES.iter().map(|p| p.to_string()).collect();
+    ignore.extend([".git".to_string(), GENIGNORE.to_string()]);
+    ignore.extend(parsed.template.ignore);
+    ignore.extend(genignore(template_dir));
+    for pattern in ignore {
+        let pattern = pattern.trim_end_matches('/').to_string();
+        config.files.exclude.push(format!("{pattern}/**"));
+        config.files.exclude.push(pattern);
+    }
+    config.files.copy_without_render = parsed.template.exclude;
+    if let Some(include) = parsed.template.include {
+        config
+            .files
+            .copy_without_render
+            .extend(not_included(template_dir, &include).map_err(&invalid)?);
+    }
+    config.validate()?;
+
+    Ok(Source {
+        resolved: ResolvedTemplate {
+            config,
+            content_dirs: vec![template_dir.to_path_buf()],
+            partials_dirs: Vec::new(),
+            warnings,
+            namespace: None,
+            globals: Default::default(),
+            format: FORMAT.to_string(),
+            skip_empty_paths: false,
+        },
+        extra_suffix: Some(LIQUID_SUFFIX),
+        rewrites,
+    })
+}
+
+/// Add a placeholder under a name Tera accepts, returning that name.
+fn insert_placeholder(
+    config: &mut TemplateConfig,
+    renames: &mut Vec<(String, String)>,
+    name: String,
+    var: VariableConfig,
+) -> String {
+    let native = name.replace('-', "_");
+    if native != name {
+        renames.push((name, native.clone()));
+    }
+    config.variables.insert(native.clone(), var);
+    native
+}
+
+fn placeholder_variable(
+    name: &str,
+    placeholder: Placeholder,
+) -> std::result::Result<VariableConfig, String> {
+    let var_type = match (placeholder.kind.as_str(), &placeholder.choices) {
+        ("string", Some(_)) => VariableType::Select,
+        ("string", None) => VariableType::String,
+        ("bool", _) => VariableType::Bool,
+        (other, _) => {
+            return Err(format!(
+                "placeholder '{name}' has unsupported type '{other}' (expected string or bool)"
+            ))
+        }
+    };
+    Ok(VariableConfig {
+        var_type,
+        prompt: placeholder.prompt,
+        default: placeholder.default,
+        choices: placeholder
+            .choices
+            .map(|choices| choices.into_iter().map(Choice::from).collect()),
+        validation: placeholder.regex,
+        ..Default::default()
+    })
+}
+
+/// Regexes that turn Liquid tag contents into Tera.
+fn rewrites(renames: &[(String, String)]) -> Vec<(Regex, String)> {
+    let mut rewrites = Vec::new();
+    for (from, to) in renames {
+        let re = format!(r"\b{}\b", regex_lite::escape(from));
+        rewrites.push((Regex::new(&re).expect("valid regex"), to.clone()));
+    }
+    for (from, to) in LIQUID_FILTERS {
+        let re = format!(r"\|\s*{from}\b");
+        rewrites.push((Regex::new(&re).expect("valid regex"), format!("| {to}")));
+    }
+    for (from, to) in LIQUID_TAGS {
+        let re = format!(r"(\{{%-?\s*){from}\b");
+        rewrites.push((Regex::new(&re).expect("valid regex"), format!("${{1}}{to}")));
+    }
+    rewrites
+}
+
+fn apply(rewrites: &[(Regex, String)], text: &str) -> String {
+    rewrites.iter().fold(text.to_string(), |text, (re, to)| {
+        re.replace_all(&text, to.as_str()).into_owned()
+    })
+}
+
+/// Built-in placeholder names used in one of `expressions` or in a tag of
+/// one of the template's text files.
+fn mentioned_names(
+    template_dir: &Path,
+    expressions: &[&str],
+) -> std::collections::BTreeSet<&'static str> {
+    let names = ["crate_type", "authors", "username"]
+        .into_iter()
+        .chain(UNSUPPORTED_BUILTINS.iter().copied());
+    let texts: Vec<String> = WalkDir::new(template_dir)
+        .into_iter()
+        .filter_entry(|e| e.file_name() != ".git")
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && !is_binary_file(e.path()))
+        .filter_map(|e| std::fs::read_to_string(e.path()).ok())
+        .collect();
+    let mut uses: Vec<&str> = expressions.to_vec();
+    for text in &texts {
+        uses.extend(template_tags(text));
+    }
+    names
+        .filter(|name| uses.iter().any(|used| used.contains(name)))
+        .collect()
+}
+
+fn genignore(template_dir: &Path) -> Vec<String> {
+    std::fs::read_to_string(template_dir.join(GENIGNORE))
+        .unwrap_or_default()
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(String::from)
+        .collect()
+}
+
+/// Paths of files `include` leaves out, which cargo-generate copies without rendering.
+fn not_included(
+    template_dir: &Path,
+    include: &[String],
+) -> std::result::Result<Vec<String>, String> {
+    let mut builder = globset::GlobSetBuilder::new();
+    for pattern in include {
+        let pattern = pattern.trim_end_matches('/');
+        for glob in [pattern.to_string(), format!("{pattern}/**")] {
+            builder.add(globset::Glob::new(&glob).map_err(|e| e.to_string())?);
+        }
+    }
+    let included = builder.build().map_err(|e| e.to_string())?;
+    Ok(WalkDir::new(template_dir)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter()
+        .filter_entry(|e| e.file_name() != ".git")
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| {
+            let rel = e
+                .path()
+                .strip_prefix(template_dir)
+                .expect("under template_dir");
+            crate::adapter::tera_name(rel)
+        })
+        .filter(|rel| !included.is_match(rel))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn maps_placeholders_and_conditionals() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("cargo-generate.toml"),
+            r#"
+[template]
+cargo_generate_version = ">=0.18"
+ignore = ["notes/"]
+
+[placeholders.gh-user]
+type = "string"
+prompt = "GitHub user?"
+regex = "^[a-z]+$"
+
+[placeholders.license]
+type = "string"
+prompt = "License?"
+choices = ["MIT", "Apache-2.0"]
+default = "MIT"
+
+[conditional.'crate_type == "lib"']
+ignore = ["src/main.rs"]
+
+[conditional.'license == "MIT"'.placeholders.mit-holder]
+type = "string"
+prompt = "Copyright holder?"
+
+[hooks]
+pre = ["pre.rhai"]
+"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("README.md"), "{{crate_type}}").unwrap();
+
+        let source = read(dir.path()).unwrap();
+        let config = &source.resolved.config;
+        let names: Vec<&str> = config.variables.keys().map(String::as_str).collect();
+        assert_eq!(
+            names,
+            vec![
+                "project_name",
+                "crate_name",
+                "crate_type",
+                "gh_user",
+                "license",
+                "mit_holder"
+            ]
+        );
+        assert_eq!(
+            config.variables["gh_user"].validation.as_deref(),
+            Some("^[a-z]+$")
+        );
+        assert_eq!(config.variables["license"].var_type, VariableType::Select);
+        assert_eq!(
+            config.variables["mit_holder"].when.as_deref(),
+            Some(r#"license == "MIT""#)
+        );
+        assert_eq!(config.files.conditional[0].pattern, "src/main.rs");
+        assert_eq!(
+            config.files.conditional[0].when,
+            r#"not crate_type == "lib""#
+        );
+        assert!(config.files.exclude.contains(&"notes/**".to_string()));
+        assert_eq!(source.resolved.warnings.len(), 1);
+        assert!(source.resolved.warnings[0].contains("Rhai"));
+
+        assert_eq!(
+            apply(
+                &source.rewrites,
+                "{% elsif gh-user %}{{ project-name | upcase }}"
+            ),
+            "{% elif gh_user %}{{ project_name | upper }}"
+        );
+    }
+
+    #[test]
+    fn unknown_placeholder_type_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("cargo-generate.toml"),
+            "[placeholders.n]\ntype = \"int\"\nprompt = \"n?\"\n",
+        )
+        .unwrap();
+
+        assert!(matches!(
+            read(dir.path()),
+            Err(DicecutError::ForeignTemplate { .. })
+        ));
+    }
+}
@@ -0,0 +1,550 @@
+pub mod cargo_generate;
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use regex_lite::Regex;
+
+use crate::adapter::{registry, tera_name, ResolvedTemplate};
+use crate::check::{check_template, Severity};
+use crate::config::schema::DEFAULT_TEMPLATES_SUFFIX;
+use crate::error::{DicecutError, Result};
+use crate::render::file::is_binary_file;
+use crate::render::walker::build_glob_set;
+
+/// Written next to the converted `diecut.toml`, listing what needs a person to finish.
+pub const REPORT_FILE: &str = "MIGRATION.md";
+
+/// Filters built into Tera. Anything else a converted file uses has to be
+/// replaced by hand.
+const TERA_FILTERS: &[&str] = &[
+    "upper",
+    "lower",
+    "trim",
+    "trim_start",
+    "trim_end",
+    "trim_start_matches",
+    "trim_end_matches",
+    "truncate",
+    "wordcount",
+    "replace",
+    "capitalize",
+    "title",
+    "linebreaksbr",
+    "indent",
+    "striptags",
+    "spaceless",
+    "urlencode",
+    "urlencode_strict",
+    "escape",
+    "escape_xml",
+    "slugify",
+    "addslashes",
+    "split",
+    "int",
+    "float",
+    "first",
+    "last",
+    "nth",
+    "join",
+    "sort",
+    "unique",
+    "slice",
+    "group_by",
+    "filter",
+    "map",
+    "concat",
+    "abs",
+    "pluralize",
+    "round",
+    "filesizeformat",
+    "length",
+    "reverse",
+    "date",
+    "json_encode",
+    "as_str",
+    "get",
+    "default",
+    "safe",
+];
+
+/// A foreign template, read and ready to be written out natively.
+pub(crate) struct Source {
+    resolved: ResolvedTemplate,
+    /// Removed from rendered file names after the template suffix, like
+    /// cargo-generate's `.liquid`.
+    extra_suffix: Option<&'static str>,
+    /// Replacements applied inside `{{ }}` and `{% %}` tags of file contents and paths.
+    rewrites: Vec<(Regex, String)>,
+}
+
+/// What `convert_template` wrote.
+pub struct ConversionReport {
+    pub template_name: String,
+    /// Format the template was read as, e.g. `cookiecutter`.
+    pub format: String,
+    /// Files written under `template/`, relative to it.
+    pub files: Vec<PathBuf>,
+    /// Everything that could not be translated, also written to [`REPORT_FILE`].
+    pub notes: Vec<String>,
+}
+
+/// Convert a Cookiecutter, Copier, or cargo-generate template (or one read
+/// by a registered adapter) into a native template in `output_dir`.
+///
+/// Variables go to `diecut.toml` and files to `template/`. Files the source
+/// format renders get the `.die` suffix only if they still contain template
+/// syntax once namespaces like `cookiecutter.` are rewritten away; everything
+/// else is copied as-is. Settings and syntax with no native equivalent are
+/// listed in the returned report and in `MIGRATION.md`.
+///
+/// `output_dir` must be missing or empty unless `overwrite` is set, and even
+/// then it must hold an earlier conversion (a `diecut.toml`). Its `template/`
+/// directory is replaced; other files are left in place.
+pub fn convert_template(
+    template_dir: &Path,
+    output_dir: &Path,
+    format: Option<&str>,
+    overwrite: bool,
+) -> Result<ConversionReport> {
+    let source = read_source(template_dir, format)?;
+    let resolved = &source.resolved;
+    if output_dir.exists() {
+        if !overwrite && !is_empty_dir(output_dir) {
+            return Err(DicecutError::OutputExists {
+                path: output_dir.to_path_buf(),
+            });
+        }
+        let canonical = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if canonical(template_dir).starts_with(canonical(output_dir)) {
+            return Err(DicecutError::ConvertOutputOverlaps {
+                path: output_dir.to_path_buf(),
+            });
+        }
+        if !is_empty_dir(output_dir) && !output_dir.join("diecut.toml").is_file() {
+            return Err(DicecutError::ConvertOutputNotTemplate {
+                path: output_dir.to_path_buf(),
+            });
+        }
+    }
+
+    let config = &resolved.config;
+    let suffix = config
+        .template
+        .templates_suffix
+        .as_deref()
+        .unwrap_or(DEFAULT_TEMPLATES_SUFFIX);
+    let exclude_set = build_glob_set(&config.files.exclude)?;
+    let copy_set = build_glob_set(&config.files.copy_without_render)?;
+
+    // Read every file before touching the output, so nothing in the source
+    // is lost if writing fails.
+    let mut notes = resolved.warnings.clone();
+    let mut converted = Vec::new();
+    for file in resolved.content_files()? {
+        let rel = tera_name(&file.rel_path);
+        if exclude_set.is_match(&rel) {
+            continue;
+        }
+        let rendered =
+            rel.ends_with(suffix) && !copy_set.is_match(&rel) && !is_binary_file(&file.src_path);
+        let text = match rendered {
+            true => std::fs::read_to_string(&file.src_path).ok(),
+            false => None,
+        };
+
+        let mut dest = source.rewrite(&rel);
+        if resolved.skip_empty_paths && dest.contains("{%") {
+            notes.push(format!(
+                "{dest}: native templates don't skip files whose path renders empty; use a [[files.conditional]] rule instead"
+            ));
+        }
+        let content = match text {
+            Some(text) => {
+                for strip in std::iter::once(suffix).chain(source.extra_suffix) {
+                    if let Some(stripped) = dest.strip_suffix(strip) {
+                        dest.truncate(stripped.len());
+                    }
+                }
+                let text = source.rewrite(&text);
+                if has_template_syntax(&text) {
+                    dest.push_str(DEFAULT_TEMPLATES_SUFFIX);
+                    review(resolved, &dest, &text, &mut notes);
+                }
+                text.into_bytes()
+            }
+            None => read_bytes(&file.src_path)?,
+        };
+        converted.push((dest, content));
+    }
+
+    // Replace only what an earlier conversion wrote; anything else the user
+    // added next to it (a README, `.git`) stays.
+    let content_dir = output_dir.join("template");
+    if content_dir.exists() {
+        std::fs::remove_dir_all(&content_dir).map_err(|e| DicecutError::Io {
+            context: format!("removing {}", content_dir.display()),
+            source: e,
+        })?;
+    }
+    create_dir(&content_dir)?;
+    let mut files = Vec::new();
+    for (dest, content) in converted {
+        let dest_path = content_dir.join(&dest);
+        if let Some(parent) = dest_path.parent() {
+            create_dir(parent)?;
+        }
+        std::fs::write(&dest_path, content).map_err(|e| DicecutError::Io {
+            context: format!("writing {}", dest_path.display()),
+            source: e,
+        })?;
+        files.push(PathBuf::from(dest));
+    }
+
+    let mut native = config.clone();
+    native.template.templates_suffix = None;
+    native.files.exclude.clear();
+    native.files.copy_without_render.clear();
+    let config_path = output_dir.join("diecut.toml");
+    let toml = toml::to_string_pretty(&native).map_err(|source| DicecutError::ConfigSerialize {
+        path: config_path.clone(),
+        source,
+    })?;
+    write_file(&config_path, &toml)?;
+
+    let check = check_template(output_dir)?;
+    for finding in &check.findings {
+        let severity = match finding.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        notes.push(format!(
+            "{}: {severity}: {}",
+            finding.location, finding.message
+        ));
+    }
+
+    let report = ConversionReport {
+        template_name: native.template.name,
+        format: resolved.format.clone(),
+        files,
+        notes,
+    };
+    write_file(&output_dir.join(REPORT_FILE), &report.to_markdown())?;
+    Ok(report)
+}
+
+impl ConversionReport {
+    fn to_markdown(&self) -> String {
+        let mut out = format!(
+            "# Migrating {}\n\nConverted from a {} template with `diecut convert`.\n\n",
+            self.template_name, self.format
+        );
+        if self.notes.is_empty() {
+            out.push_str("Everything was translated; nothing needs a manual change.\n");
+        } else {
+            out.push_str("These need a manual change:\n\n");
+            for note in &self.notes {
+                out.push_str(&format!("- {note}\n"));
+            }
+        }
+        out
+    }
+}
+
+impl Source {
+    /// Apply the rewrites to every tag in `text`, leaving the rest untouched.
+    fn rewrite(&self, text: &str) -> String {
+        if self.rewrites.is_empty() {
+            return text.to_string();
+        }
+        tag_regex()
+            .replace_all(text, |caps: &regex_lite::Captures| {
+                self.rewrites
+                    .iter()
+                    .fold(caps[0].to_string(), |tag, (re, to)| {
+                        re.replace_all(&tag, to.as_str()).into_owned()
+                    })
+            })
+            .into_owned()
+    }
+}
+
+/// Pick the reader for `template_dir`: the adapter named `format`, the one
+/// that detects the directory, or the cargo-generate reader.
+fn read_source(template_dir: &Path, format: Option<&str>) -> Result<Source> {
+    let not_convertible = |reason: &str| DicecutError::NotConvertible {
+        path: template_dir.to_path_buf(),
+        reason: reason.to_string(),
+    };
+    let registry = registry();
+    let format = match format {
+        Some(format) => format.to_string(),
+        None => match registry.detect(template_dir) {
+            Some(adapter) => adapter.name().to_string(),
+            None if cargo_generate::config_file(template_dir).is_some() => {
+                cargo_generate::FORMAT.to_string()
+            }
+            None => {
+                return Err(not_convertible(
+                    "no cookiecutter.json, copier.yml, or cargo-generate.toml found",
+                ))
+            }
+        },
+    };
+
+    match format.as_str() {
+        "native" => Err(not_convertible("it is already a diecut template")),
+        cargo_generate::FORMAT => cargo_generate::read(template_dir),
+        _ => {
+            let resolved = registry.resolve(template_dir, Some(&format))?;
+            let rewrites = match &resolved.namespace {
+                Some(namespace) => vec![(
+                    Regex::new(&format!(r"\b{}\.", regex_lite::escape(namespace)))
+                        .expect("valid regex"),
+                    String::new(),
+                )],
+                None => Vec::new(),
+            };
+            Ok(Source {
+                resolved,
+                extra_suffix: None,
+                rewrites,
+            })
+        }
+    }
+}
+
+/// Note what a converted file uses that a native template doesn't have:
+/// unknown filters, the namespace object itself, and adapter globals.
+fn review(resolved: &ResolvedTemplate, dest: &str, text: &str, notes: &mut Vec<String>) {
+    let location = format!("template/{dest}");
+    let filter = Regex::new(r"\|\s*([A-Za-z_][A-Za-z0-9_]*)").expect("valid regex");
+    let mut unknown = BTreeSet::new();
+    let mut globals = BTreeSet::new();
+    let mut uses_namespace = false;
+    for tag in template_tags(text) {
+        for caps in filter.captures_iter(tag) {
+            let name = caps.get(1).map_or("", |m| m.as_str());
+            if !TERA_FILTERS.contains(&name) {
+                unknown.insert(name.to_string());
+            }
+        }
+        if let Some(namespace) = &resolved.namespace {
+            uses_namespace |= Regex::new(&format!(r"\b{}\b", regex_lite::escape(namespace)))
+                .expect("valid regex")
+                .is_match(tag);
+        }
+        for name in resolved.globals.keys() {
+            if tag.contains(name.as_str()) {
+                globals.insert(name.clone());
+            }
+        }
+    }
+
+    for name in unknown {
+        notes.push(format!(
+            "{location}: uses the filter '{name}', which Tera doesn't have"
+        ));
+    }
+    if uses_namespace {
+        notes.push(format!(
+            "{location}: uses the '{}' object itself; refer to variables by name instead",
+            resolved.namespace.as_deref().unwrap_or_default()
+        ));
+    }
+    for name in globals {
+        notes.push(format!(
+            "{location}: uses '{name}', which native templates don't provide"
+        ));
+    }
+}
+
+/// Matches `{{ ... }}`, `{% ... %}`, and `{# ... #}` spans.
+fn tag_regex() -> Regex {
+    Regex::new(r"(?s)\{\{.*?\}\}|\{%.*?%\}|\{#.*?#\}").expect("valid regex")
+}
+
+/// The template tags in `text`, delimiters included.
+pub(crate) fn template_tags(text: &str) -> Vec<&str> {
+    tag_regex().find_iter(text).map(|m| m.as_str()).collect()
+}
+
+fn is_empty_dir(path: &Path) -> bool {
+    std::fs::read_dir(path).is_ok_and(|mut entries| entries.next().is_none())
+}
+
+fn has_template_syntax(text: &str) -> bool {
+    text.contains("{{") || text.contains("{%") || text.contains("{#")
+}
+
+fn create_dir(path: &Path) -> Result<()> {
+    std::fs::create_dir_all(path).map_err(|e| DicecutError::Io {
+        context: format!("creating {}", path.display()),
+        source: e,
+    })
+}
+
+fn read_bytes(path: &Path) -> Result<Vec<u8>> {
+    std::fs::read(path).map_err(|e| DicecutError::Io {
+        context: format!("reading {}", path.display()),
+        source: e,
+    })
+}
+
+fn write_file(path: &Path, content: &str) -> Result<()> {
+    std::fs::write(path, content).map_err(|e| DicecutError::Io {
+        context: format!("writing {}", path.display()),
+        source: e,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::write_files;
+    use std::fs;
+
+    #[test]
+    fn converts_cookiecutter_template() {
+        let src = tempfile::tempdir().unwrap();
+        write_files(
+            src.path(),
+            &[
+                (
+                    "cookiecutter.json",
+                    r#"{"project_name": "Demo", "project_slug": "{{ cookiecutter.project_name | slugify }}"}"#,
+                ),
+                (
+                    "{{cookiecutter.project_slug}}/README.md",
+                    "# {{ cookiecutter.project_name }}\nSee cookiecutter.io\n",
+                ),
+                ("{{cookiecutter.project_slug}}/LICENSE", "MIT\n"),
+                (
+                    "{{cookiecutter.project_slug}}/{{cookiecutter.project_slug}}/__init__.py",
+                    "",
+                ),
+                (
+                    "{{cookiecutter.project_slug}}/setup.py",
+                    "name = {{ cookiecutter.project_slug | jsonify }}\n",
+                ),
+            ],
+        );
+        let out = tempfile::tempdir().unwrap();
+
+        let report = convert_template(src.path(), out.path(), None, false).unwrap();
+        assert_eq!(report.format, "cookiecutter");
+        let template = out.path().join("template");
+        assert_eq!(
+            fs::read_to_string(template.join("README.md.die")).unwrap(),
+            "# {{ project_name }}\nSee cookiecutter.io\n"
+        );
+        assert!(template.join("LICENSE").is_file());
+        assert!(template.join("{{project_slug}}/__init__.py").is_file());
+        assert!(report
+            .notes
+            .iter()
+            .any(|n| n.contains("setup.py.die") && n.contains("'jsonify'")));
+
+        let toml = fs::read_to_string(out.path().join("diecut.toml")).unwrap();
+        assert!(toml.contains("[variables.project_slug]"), "{toml}");
+        assert!(!toml.contains("templates_suffix"), "{toml}");
+        let report_file = fs::read_to_string(out.path().join(REPORT_FILE)).unwrap();
+        assert!(report_file.contains("jsonify"));
+    }
+
+    #[test]
+    fn converts_copier_suffix_to_die() {
+        let src = tempfile::tempdir().unwrap();
+        write_files(
+            src.path(),
+            &[
+                ("copier.yml", "name: demo\n_tasks: [\"echo hi\"]\n"),
+                ("README.md.jinja", "# {{ name }}\n"),
+                ("plain.txt.jinja", "no tags here\n"),
+                ("raw.txt", "{{ left alone }}\n"),
+                ("{% if name %}NOTES.md{% endif %}", ""),
+            ],
+        );
+        let out = tempfile::tempdir().unwrap();
+
+        let report = convert_template(src.path(), out.path(), None, false).unwrap();
+        let template = out.path().join("template");
+        assert!(template.join("README.md.die").is_file());
+        assert!(template.join("plain.txt").is_file());
+        assert!(template.join("raw.txt").is_file());
+        assert!(!template.join("copier.yml").exists());
+        assert!(report.notes.iter().any(|n| n.contains("_tasks")));
+        assert!(report
+            .notes
+            .iter()
+            .any(|n| n.contains("NOTES.md") && n.contains("[[files.conditional]]")));
+    }
+
+    #[test]
+    fn overwrite_never_deletes_the_template() {
+        let src = tempfile::tempdir().unwrap();
+        let template_dir = src.path().join("cc");
+        write_files(
+            &template_dir,
+            &[
+                ("cookiecutter.json", r#"{"name": "x"}"#),
+                ("{{cookiecutter.name}}/README.md", "{{ cookiecutter.name }}"),
+            ],
+        );
+
+        for output in [template_dir.clone(), src.path().to_path_buf()] {
+            assert!(matches!(
+                convert_template(&template_dir, &output, None, true),
+                Err(DicecutError::ConvertOutputOverlaps { .. })
+            ));
+        }
+        assert!(template_dir.join("cookiecutter.json").is_file());
+        assert!(template_dir
+            .join("{{cookiecutter.name}}/README.md")
+            .is_file());
+    }
+
+    #[test]
+    fn overwrite_replaces_only_an_earlier_conversion() {
+        let src = tempfile::tempdir().unwrap();
+        write_files(
+            src.path(),
+            &[
+                ("cookiecutter.json", r#"{"name": "x"}"#),
+                ("{{cookiecutter.name}}/README.md", "{{ cookiecutter.name }}"),
+            ],
+        );
+        let out = tempfile::tempdir().unwrap();
+        write_files(out.path(), &[("notes.txt", "mine\n")]);
+
+        assert!(matches!(
+            convert_template(src.path(), out.path(), None, true),
+            Err(DicecutError::ConvertOutputNotTemplate { .. })
+        ));
+        assert!(out.path().join("notes.txt").is_file());
+
+        fs::remove_file(out.path().join("notes.txt")).unwrap();
+        convert_template(src.path(), out.path(), None, false).unwrap();
+        write_files(
+            out.path(),
+            &[("notes.txt", "mine\n"), ("template/stale.txt", "old\n")],
+        );
+        convert_template(src.path(), out.path(), None, true).unwrap();
+        assert!(out.path().join("notes.txt").is_file());
+        assert!(!out.path().join("template/stale.txt").exists());
+        assert!(out.path().join("template/README.md.die").is_file());
+    }
+
+    #[test]
+    fn native_template_is_not_convertible() {
+        let src = tempfile::tempdir().unwrap();
+        write_files(src.path(), &[("diecut.toml", "[template]\nname = \"x\"\n")]);
+        let out = tempfile::tempdir().unwrap();
+
+        assert!(matches!(
+            convert_template(src.path(), &out.path().join("x"), None, false),
+            Err(DicecutError::NotConvertible { .. })
+        ));
+    }
+}
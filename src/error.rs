@@ -18,12 +18,97 @@ pub enum DicecutError {
         source: toml::de::Error,
     },
 
+    #[error("Failed to serialize {path}")]
+    ConfigSerialize {
+        path: PathBuf,
+        #[source]
+        source: toml::ser::Error,
+    },
+
+    #[error("Failed to parse user config {path}")]
+    #[diagnostic(help("Check the TOML syntax, or inspect it with `diecut config list`"))]
+    UserConfigParse {
+        path: PathBuf,
+        #[source]
+        source: toml::de::Error,
+    },
+
+    #[error("Failed to edit user config {path}")]
+    #[diagnostic(help("Check the TOML syntax, or inspect it with `diecut config list`"))]
+    UserConfigEdit {
+        path: PathBuf,
+        #[source]
+        source: toml_edit::TomlError,
+    },
+
+    #[error("Unknown config key '{key}'")]
+    #[diagnostic(help("Supported keys: protocol, abbreviations.<prefix>"))]
+    UnknownConfigKey { key: String },
+
+    #[error("Invalid value for config key '{key}': {reason}")]
+    InvalidConfigValue { key: String, reason: String },
+
+    #[error("This template requires diecut {requirement}, but diecut {current} is installed")]
+    #[diagnostic(help("{install_hint}"))]
+    IncompatibleDiecutVersion {
+        requirement: String,
+        current: String,
+        install_hint: String,
+    },
+
+    #[error("Invalid {field} '{value}' in diecut.toml")]
+    #[diagnostic(help("Use a semantic version such as \"0.4.0\""))]
+    InvalidDiecutVersion { field: &'static str, value: String },
+
+    #[error("Template inheritance cycle: {chain}")]
+    #[diagnostic(help("Check the `extends` field in each diecut.toml along the chain"))]
+    ExtendsCycle { chain: String },
+
+    #[error("Unknown template format '{format}'")]
+    #[diagnostic(help("Known formats: {known}"))]
+    UnknownTemplateFormat { format: String, known: String },
+
+    #[error("Invalid {format} template config {path}: {reason}")]
+    ForeignTemplate {
+        format: &'static str,
+        path: PathBuf,
+        reason: String,
+    },
+
+    #[error("Cannot convert {path}: {reason}")]
+    #[diagnostic(help(
+        "diecut convert reads Cookiecutter, Copier, and cargo-generate templates; pass --format if the format isn't detected"
+    ))]
+    NotConvertible { path: PathBuf, reason: String },
+
+    #[error("Output directory {path} contains the template being converted")]
+    #[diagnostic(help("Choose an output directory outside the template"))]
+    ConvertOutputOverlaps { path: PathBuf },
+
+    #[error("Output directory {path} is not empty and has no diecut.toml")]
+    #[diagnostic(help(
+        "--overwrite only replaces an earlier conversion; choose a new or empty output directory"
+    ))]
+    ConvertOutputNotTemplate { path: PathBuf },
+
     #[error("Invalid variable definition for '{name}': {reason}")]
     ConfigInvalidVariable { name: String, reason: String },
 
+    #[error("Invalid [[files.each]] rule for '{pattern}': {reason}")]
+    InvalidEachRule { pattern: String, reason: String },
+
     #[error("Validation failed for variable '{name}': {message}")]
     ValidationFailed { name: String, message: String },
 
+    #[error("{} variable values failed validation", .problems.len())]
+    #[diagnostic(help(
+        "Fix the values passed with --data, or the template defaults used with --defaults"
+    ))]
+    InvalidValues {
+        #[related]
+        problems: Vec<DicecutError>,
+    },
+
     #[error("Template rendering failed for '{file}'")]
     #[diagnostic(help("Check your Tera template syntax"))]
     RenderError {
@@ -61,6 +146,10 @@ pub enum DicecutError {
         source: globset::Error,
     },
 
+    #[error("Input is disabled, but these variables have no value:\n{missing}")]
+    #[diagnostic(help("Pass each one with -d NAME=VALUE, or give it a default in diecut.toml"))]
+    MissingValues { missing: String },
+
     #[error("Prompt cancelled by user")]
     PromptCancelled,
 
@@ -78,6 +167,33 @@ pub enum DicecutError {
         source: tera::Error,
     },
 
+    #[error("Invalid choices_from expression for variable '{name}'")]
+    ChoicesEvaluation {
+        name: String,
+        #[source]
+        source: tera::Error,
+    },
+
+    #[error("Invalid validator for variable '{name}'")]
+    ValidatorEvaluation {
+        name: String,
+        #[source]
+        source: tera::Error,
+    },
+
+    #[error("Invalid templated default for variable '{name}'")]
+    DefaultEvaluation {
+        name: String,
+        #[source]
+        source: tera::Error,
+    },
+
+    #[error("Variables depend on each other in a cycle: {cycle}")]
+    #[diagnostic(help(
+        "Break the cycle by removing one of these references from a `when`, `computed`, or `default`"
+    ))]
+    VariableCycle { cycle: String },
+
     #[error("Invalid git protocol value '{value}' in {config_key}")]
     #[diagnostic(help("Expected 'ssh' or 'https'"))]
     InvalidProtocol {
@@ -117,6 +233,30 @@ pub enum DicecutError {
     ))]
     NoAnswerFile { path: PathBuf },
 
+    #[error("No template source given")]
+    #[diagnostic(help(
+        "Pass a template explicitly; only git template sources are recorded in .diecut-answers.toml"
+    ))]
+    MissingTemplateSource,
+
+    #[error("Cannot update project at {path}: {reason}")]
+    #[diagnostic(help(
+        "`diecut update` needs a project generated from a git template, with its commit recorded in .diecut-answers.toml"
+    ))]
+    UpdateUnsupported { path: PathBuf, reason: String },
+
+    #[error("Failed to merge template changes into '{file}': {reason}")]
+    MergeFailed { file: String, reason: String },
+
+    #[error("Template check found {errors} error(s)")]
+    CheckFailed { errors: usize },
+
+    #[error("Failed to read data file {path}: {reason}")]
+    #[diagnostic(help(
+        "Data files hold a table of variable names to values, as .json, .toml, .yaml or .yml"
+    ))]
+    DataFileParse { path: PathBuf, reason: String },
+
     #[error("Failed to parse answers file in {path}")]
     #[diagnostic(help("Check the TOML syntax in .diecut-answers.toml"))]
     AnswerFileParseError {
@@ -126,4 +266,15 @@ pub enum DicecutError {
     },
 }
 
+impl DicecutError {
+    /// A single problem as-is, or several reported together as [`DicecutError::InvalidValues`].
+    pub(crate) fn from_problems(mut problems: Vec<DicecutError>) -> Self {
+        if problems.len() == 1 {
+            problems.remove(0)
+        } else {
+            DicecutError::InvalidValues { problems }
+        }
+    }
+}
+
 pub type Result<T> = std::result::Result<T, DicecutError>;
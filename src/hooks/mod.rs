@@ -1,26 +1,68 @@
+use std::collections::BTreeMap;
 use std::path::Path;
 
+use tera::Value;
+
+use crate::answers::data::VAR_ENV_PREFIX;
 use crate::config::schema::HooksConfig;
 use crate::error::{DicecutError, Result};
 
+/// Run the `pre_prompt` hook in the template directory, before any variable is collected.
+pub fn run_pre_prompt(hooks: &HooksConfig, template_dir: &Path) -> Result<()> {
+    run_hook("pre_prompt", hooks.pre_prompt.as_deref(), template_dir, &[])
+}
+
+/// Run the `pre_create` hook in the template directory, before anything is written.
+///
+/// Each answer is passed as a `DIECUT_VAR_<name>` environment variable, the
+/// same form `diecut new` reads them from; lists and tables are JSON.
+pub fn run_pre_create(
+    hooks: &HooksConfig,
+    template_dir: &Path,
+    variables: &BTreeMap<String, Value>,
+) -> Result<()> {
+    let env: Vec<(String, String)> = variables
+        .iter()
+        .map(|(name, value)| {
+            let value = match value {
+                Value::String(s) => s.clone(),
+                other => other.to_string(),
+            };
+            (format!("{VAR_ENV_PREFIX}{name}"), value)
+        })
+        .collect();
+    run_hook(
+        "pre_create",
+        hooks.pre_create.as_deref(),
+        template_dir,
+        &env,
+    )
+}
+
 pub fn run_post_create(hooks: &HooksConfig, output_dir: &Path) -> Result<()> {
-    if let Some(cmd) = &hooks.post_create {
-        let status = std::process::Command::new("sh")
-            .arg("-c")
-            .arg(cmd)
-            .current_dir(output_dir)
-            .status()
-            .map_err(|e| DicecutError::HookError {
-                hook: "post_create".to_string(),
-                message: format!("failed to execute: {e}"),
-            })?;
+    run_hook("post_create", hooks.post_create.as_deref(), output_dir, &[])
+}
+
+fn run_hook(hook: &str, cmd: Option<&str>, dir: &Path, env: &[(String, String)]) -> Result<()> {
+    let Some(cmd) = cmd else {
+        return Ok(());
+    };
+    let status = std::process::Command::new("sh")
+        .arg("-c")
+        .arg(cmd)
+        .current_dir(dir)
+        .envs(env.iter().map(|(k, v)| (k, v)))
+        .status()
+        .map_err(|e| DicecutError::HookError {
+            hook: hook.to_string(),
+            message: format!("failed to execute: {e}"),
+        })?;
 
-        if !status.success() {
-            return Err(DicecutError::HookError {
-                hook: "post_create".to_string(),
-                message: format!("exited with status {status}"),
-            });
-        }
+    if !status.success() {
+        return Err(DicecutError::HookError {
+            hook: hook.to_string(),
+            message: format!("exited with status {status}"),
+        });
     }
     Ok(())
 }
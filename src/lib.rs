@@ -1,33 +1,56 @@
 pub mod adapter;
 pub mod answers;
+pub mod check;
 pub mod config;
+pub mod convert;
 pub mod error;
 pub mod hooks;
 pub mod prompt;
 pub mod render;
 pub mod template;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod update;
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
 
 use console::style;
 use tera::Value;
 
-use crate::adapter::resolve_template;
-use crate::answers::TemplateOrigin;
+use crate::adapter::resolve_template_as;
+use crate::answers::{load_answers_file, SavedAnswers, TemplateOrigin};
 use crate::error::{DicecutError, Result};
 use crate::prompt::{collect_variables, PromptOptions};
-use crate::render::{build_context, execute_plan, plan_render, GeneratedProject, GenerationPlan};
+use crate::render::{execute_plan, plan_render, GeneratedProject, GenerationPlan};
 use crate::template::{get_or_clone, resolve_source, GitProtocol, ResolveOptions, TemplateSource};
 
+#[derive(Default)]
 pub struct GenerateOptions {
-    pub template: String,
+    /// Template source. Falls back to the source recorded in `answers_file` when `None`.
+    pub template: Option<String>,
+    /// Branch, tag, or commit SHA to check out for git templates.
+    pub git_ref: Option<String>,
+    /// Adapter to read the template with (`--format`); detected when `None`.
+    pub format: Option<String>,
     pub output: Option<String>,
+    /// A saved `.diecut-answers.toml` (or the project directory containing one)
+    /// whose answers are used instead of prompting.
+    pub answers_file: Option<PathBuf>,
     pub data: Vec<(String, String)>,
+    /// A JSON, TOML, or YAML file of variable values (`--data-file`).
+    pub data_file: Option<PathBuf>,
     pub defaults: bool,
+    /// Never prompt; fail listing every variable that has no value.
+    pub no_input: bool,
     pub overwrite: bool,
     pub no_hooks: bool,
+    /// Planning for `--dry-run`: the `pre_prompt` and `pre_create` hooks are
+    /// skipped, so previewing a template never runs its code.
+    pub dry_run: bool,
     pub protocol: GitProtocol,
+    /// User-defined abbreviations from the user config (`company:` → URL template).
+    pub abbreviations: HashMap<String, String>,
 }
 
 /// Everything needed to execute a generation that has been planned but not yet written.
@@ -37,21 +60,48 @@ pub struct FullGenerationPlan {
     pub config: crate::config::schema::TemplateConfig,
     pub variables: BTreeMap<String, Value>,
     origin: TemplateOrigin,
+    /// Format the template was read with, when given with `--format` (or
+    /// recorded in the answers file).
+    format: Option<String>,
     pub no_hooks: bool,
 }
 
+impl FullGenerationPlan {
+    /// Replace every occurrence of a secret variable's value in `text` with `********`,
+    /// for showing planned output without leaking tokens.
+    pub fn redact_secrets(&self, text: &str) -> String {
+        let mut secrets: Vec<&str> = self
+            .config
+            .variables
+            .iter()
+            .filter(|(_, var)| var.secret)
+            .filter_map(|(name, _)| self.variables.get(name)?.as_str())
+            .filter(|value| !value.is_empty())
+            .collect();
+        // Longest first, so a secret containing another is masked whole.
+        secrets.sort_by_key(|secret| std::cmp::Reverse(secret.len()));
+
+        let mut redacted = text.to_string();
+        for secret in secrets {
+            redacted = redacted.replace(secret, "********");
+        }
+        redacted
+    }
+}
+
 /// Plan a project generation: resolve template, collect variables, render in memory.
 ///
-/// This performs all preparation (template resolution, variable collection, pre-generate
-/// hooks, and rendering) but does **not** write any files to disk.
+/// This performs all preparation (template resolution, the `pre_prompt` hook, variable
+/// collection, the `pre_create` hook, and rendering) but does **not** write any files
+/// to disk, so a failing hook stops generation before the output directory exists.
+/// Hooks are skipped when planning a dry run.
 pub fn plan_generation(options: GenerateOptions) -> Result<FullGenerationPlan> {
-    let source = resolve_source(
-        &options.template,
-        ResolveOptions {
-            protocol: options.protocol,
-            ..Default::default()
-        },
-    )?;
+    let saved = options
+        .answers_file
+        .as_deref()
+        .map(load_answers_file)
+        .transpose()?;
+    let source = resolve_template_source(&options, saved.as_ref())?;
     let (template_dir, origin) = match &source {
         TemplateSource::Local(path) => (path.clone(), TemplateOrigin::Local),
         TemplateSource::Git {
@@ -76,20 +126,20 @@ pub fn plan_generation(options: GenerateOptions) -> Result<FullGenerationPlan> {
                     url: url.clone(),
                     git_ref: git_ref.clone(),
                     commit_sha,
+                    subpath: subpath.clone(),
                 },
             )
         }
     };
 
-    let resolved = resolve_template(&template_dir)?;
+    // A format forced when the project was generated applies again on replay.
+    let format = options
+        .format
+        .clone()
+        .or_else(|| saved.as_ref().and_then(|saved| saved.format.clone()));
+    let resolved = resolve_template_as(&template_dir, format.as_deref())?;
 
-    for warning in &resolved.warnings {
-        eprintln!(
-            "{} {}",
-            style("warning:").yellow().bold(),
-            style(warning).yellow()
-        );
-    }
+    print_warnings(&resolved.warnings);
 
     if !options.no_hooks
         && matches!(origin, TemplateOrigin::Git { .. })
@@ -133,13 +183,31 @@ pub fn plan_generation(options: GenerateOptions) -> Result<FullGenerationPlan> {
         }
     }
 
+    let file_values = options
+        .data_file
+        .as_deref()
+        .map(answers::load_data_file)
+        .transpose()?
+        .unwrap_or_default();
     let prompt_options = PromptOptions {
         data_overrides: options.data.into_iter().collect(),
+        env_values: answers::data_from_env(),
+        file_values,
+        answers: saved.as_ref().map(SavedAnswers::values).unwrap_or_default(),
         use_defaults: options.defaults,
+        no_input: options.no_input,
+        ..Default::default()
     };
+    let run_hooks = !options.no_hooks && !options.dry_run;
+    if run_hooks {
+        hooks::run_pre_prompt(&resolved.config.hooks, &template_dir)?;
+    }
     let variables = collect_variables(&resolved.config, &prompt_options)?;
+    if run_hooks {
+        hooks::run_pre_create(&resolved.config.hooks, &template_dir, &variables)?;
+    }
 
-    let context = build_context(&variables);
+    let context = resolved.render_context(&variables);
 
     let render_plan = plan_render(&resolved, &variables, &context)?;
 
@@ -149,10 +217,54 @@ pub fn plan_generation(options: GenerateOptions) -> Result<FullGenerationPlan> {
         config: resolved.config,
         variables,
         origin,
+        format,
         no_hooks: options.no_hooks,
     })
 }
 
+/// Work out where the template for a generation comes from.
+///
+/// An explicit `template` wins; otherwise the source recorded in the saved
+/// answers (URL, ref, and subpath) is reused. An explicit `git_ref` overrides
+/// any ref from the template argument or the saved answers.
+pub fn resolve_template_source(
+    options: &GenerateOptions,
+    saved: Option<&SavedAnswers>,
+) -> Result<TemplateSource> {
+    if let Some(template) = &options.template {
+        return resolve_source(
+            template,
+            ResolveOptions {
+                git_ref: options.git_ref.as_deref(),
+                protocol: options.protocol,
+                user_abbreviations: Some(&options.abbreviations),
+            },
+        );
+    }
+
+    match saved {
+        Some(saved) if !saved.template_source.is_empty() => Ok(TemplateSource::Git {
+            url: saved.template_source.clone(),
+            git_ref: options
+                .git_ref
+                .clone()
+                .or_else(|| saved.template_ref.clone()),
+            subpath: saved.template_subpath.clone(),
+        }),
+        _ => Err(DicecutError::MissingTemplateSource),
+    }
+}
+
+pub(crate) fn print_warnings(warnings: &[String]) {
+    for warning in warnings {
+        eprintln!(
+            "{} {}",
+            style("warning:").yellow().bold(),
+            style(warning).yellow()
+        );
+    }
+}
+
 /// Execute a previously planned generation: write files, answers, and run post-generate hooks.
 pub fn execute_generation(plan: FullGenerationPlan) -> Result<GeneratedProject> {
     std::fs::create_dir_all(&plan.output_dir).map_err(|e| DicecutError::Io {
@@ -167,6 +279,7 @@ pub fn execute_generation(plan: FullGenerationPlan) -> Result<GeneratedProject>
         &plan.config,
         &plan.variables,
         &plan.origin,
+        plan.format.as_deref(),
     )?;
 
     if !plan.no_hooks {
@@ -223,13 +336,11 @@ default = "my-project"
         let output_dir = tempfile::tempdir().unwrap();
 
         let options = GenerateOptions {
-            template: template_dir.path().display().to_string(),
+            template: Some(template_dir.path().display().to_string()),
             output: Some(output_dir.path().display().to_string()),
             data: vec![("project_name".to_string(), "test-proj".to_string())],
-            defaults: false,
-            overwrite: false,
             no_hooks: true,
-            protocol: GitProtocol::default(),
+            ..Default::default()
         };
 
         let plan = plan_generation(options).unwrap();
@@ -239,16 +350,54 @@ default = "my-project"
         assert_eq!(plan.variables.get("project_name").unwrap(), "test-proj");
     }
 
+    /// Secrets come from `DIECUT_SECRET_<NAME>` and are masked in planned output.
+    #[test]
+    #[serial_test::serial]
+    fn test_secret_from_env_is_redacted() {
+        let template_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            template_dir.path().join("diecut.toml"),
+            r#"
+[template]
+name = "secrets"
+
+[variables.api_token]
+type = "string"
+secret = true
+"#,
+        )
+        .unwrap();
+        fs::create_dir_all(template_dir.path().join("template")).unwrap();
+        fs::write(
+            template_dir.path().join("template/.env.die"),
+            "TOKEN={{ api_token }}\n",
+        )
+        .unwrap();
+        let output_dir = tempfile::tempdir().unwrap();
+
+        std::env::set_var("DIECUT_SECRET_API_TOKEN", "s3cr3t-value");
+        let plan = plan_generation(GenerateOptions {
+            template: Some(template_dir.path().display().to_string()),
+            output: Some(output_dir.path().display().to_string()),
+            no_hooks: true,
+            ..Default::default()
+        });
+        std::env::remove_var("DIECUT_SECRET_API_TOKEN");
+        let plan = plan.unwrap();
+
+        assert_eq!(plan.variables["api_token"], "s3cr3t-value");
+        let content = String::from_utf8_lossy(&plan.render_plan.files[0].content);
+        assert_eq!(content, "TOKEN=s3cr3t-value\n");
+        assert_eq!(plan.redact_secrets(&content), "TOKEN=********\n");
+    }
+
     #[test]
     fn test_plan_generation_template_missing() {
         let options = GenerateOptions {
-            template: "/nonexistent/path/to/template".to_string(),
-            output: None,
-            data: vec![],
+            template: Some("/nonexistent/path/to/template".to_string()),
             defaults: true,
-            overwrite: false,
             no_hooks: true,
-            protocol: GitProtocol::default(),
+            ..Default::default()
         };
 
         let result = plan_generation(options);
@@ -270,13 +419,12 @@ default = "my-project"
         fs::write(output_dir.path().join("existing.txt"), "exists").unwrap();
 
         let options = GenerateOptions {
-            template: template_dir.path().display().to_string(),
+            template: Some(template_dir.path().display().to_string()),
             output: Some(output_dir.path().display().to_string()),
-            data: vec![],
             defaults: true,
             overwrite,
             no_hooks: true,
-            protocol: GitProtocol::default(),
+            ..Default::default()
         };
 
         let result = plan_generation(options);
@@ -298,13 +446,11 @@ default = "my-project"
         let output_path = output_parent.path().join("new_project");
 
         let options = GenerateOptions {
-            template: template_dir.path().display().to_string(),
+            template: Some(template_dir.path().display().to_string()),
             output: Some(output_path.display().to_string()),
             data: vec![("project_name".to_string(), "test".to_string())],
-            defaults: false,
-            overwrite: false,
             no_hooks: true,
-            protocol: GitProtocol::default(),
+            ..Default::default()
         };
 
         let plan = plan_generation(options).unwrap();
@@ -331,13 +477,12 @@ default = "my-project"
         let output_dir = tempfile::tempdir().unwrap();
 
         let options = GenerateOptions {
-            template: template_dir.path().display().to_string(),
+            template: Some(template_dir.path().display().to_string()),
             output: Some(output_dir.path().display().to_string()),
             data: vec![("project_name".to_string(), "test-project".to_string())],
-            defaults: false,
             overwrite: true,
             no_hooks: true,
-            protocol: GitProtocol::default(),
+            ..Default::default()
         };
 
         let plan = plan_generation(options).unwrap();
@@ -374,13 +519,12 @@ default = "test"
         let output_dir = tempfile::tempdir().unwrap();
 
         let options = GenerateOptions {
-            template: template_dir.path().display().to_string(),
+            template: Some(template_dir.path().display().to_string()),
             output: Some(output_dir.path().display().to_string()),
-            data: vec![],
             defaults: true,
             overwrite: true,
             no_hooks: true,
-            protocol: GitProtocol::default(),
+            ..Default::default()
         };
 
         let plan = plan_generation(options).unwrap();
@@ -393,6 +537,191 @@ default = "test"
         );
     }
 
+    fn create_template_with_hooks(dir: &std::path::Path, hooks: &str) {
+        let config = format!(
+            r#"
+[template]
+name = "test-with-hooks"
+
+[hooks]
+{hooks}
+
+[variables.name]
+type = "string"
+"#
+        );
+        fs::write(dir.join("diecut.toml"), config).unwrap();
+        fs::create_dir_all(dir.join("template")).unwrap();
+        fs::write(dir.join("template/README.md"), "test").unwrap();
+    }
+
+    #[test]
+    fn test_pre_prompt_failure_stops_before_prompting() {
+        let template_dir = tempfile::tempdir().unwrap();
+        create_template_with_hooks(template_dir.path(), r#"pre_prompt = "exit 3""#);
+        let output_dir = tempfile::tempdir().unwrap();
+        let output = output_dir.path().join("out");
+
+        // `name` has no value, so prompting would fail with a different error.
+        let result = plan_generation(GenerateOptions {
+            template: Some(template_dir.path().display().to_string()),
+            output: Some(output.display().to_string()),
+            no_input: true,
+            ..Default::default()
+        });
+        match result {
+            Err(DicecutError::HookError { hook, .. }) => assert_eq!(hook, "pre_prompt"),
+            Err(other) => panic!("expected the pre_prompt hook to fail, got {other:?}"),
+            Ok(_) => panic!("expected the pre_prompt hook to fail"),
+        }
+        assert!(!output.exists());
+    }
+
+    #[test]
+    fn test_dry_run_does_not_run_hooks() {
+        let template_dir = tempfile::tempdir().unwrap();
+        create_template_with_hooks(
+            template_dir.path(),
+            r#"pre_prompt = "touch pre_prompt_ran"
+pre_create = "touch pre_create_ran""#,
+        );
+        let output_dir = tempfile::tempdir().unwrap();
+
+        plan_generation(GenerateOptions {
+            template: Some(template_dir.path().display().to_string()),
+            output: Some(output_dir.path().join("out").display().to_string()),
+            data: vec![("name".to_string(), "x".to_string())],
+            no_input: true,
+            dry_run: true,
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert!(!template_dir.path().join("pre_prompt_ran").exists());
+        assert!(!template_dir.path().join("pre_create_ran").exists());
+    }
+
+    #[rstest]
+    #[case("custom", true)]
+    #[case("other", false)]
+    fn test_pre_create_sees_answers_in_template_dir(#[case] name: &str, #[case] passes: bool) {
+        let template_dir = tempfile::tempdir().unwrap();
+        create_template_with_hooks(
+            template_dir.path(),
+            r#"pre_create = 'test "$DIECUT_VAR_name" = custom && test -f diecut.toml'"#,
+        );
+        let output_dir = tempfile::tempdir().unwrap();
+        let output = output_dir.path().join("out");
+
+        let result = generate(GenerateOptions {
+            template: Some(template_dir.path().display().to_string()),
+            output: Some(output.display().to_string()),
+            data: vec![("name".to_string(), name.to_string())],
+            no_input: true,
+            ..Default::default()
+        });
+        if passes {
+            result.unwrap();
+            assert!(output.join("README.md").exists());
+        } else {
+            assert!(matches!(
+                result,
+                Err(DicecutError::HookError { ref hook, .. }) if hook == "pre_create"
+            ));
+            assert!(!output.exists());
+        }
+    }
+
+    #[test]
+    fn test_plan_generation_with_answers_file_keeps_types() {
+        let template_dir = tempfile::tempdir().unwrap();
+        let config = r#"
+[template]
+name = "typed"
+
+[variables.port]
+type = "int"
+default = 8080
+
+[variables.features]
+type = "multiselect"
+choices = ["a,b", "c"]
+"#;
+        fs::write(template_dir.path().join("diecut.toml"), config).unwrap();
+        fs::create_dir_all(template_dir.path().join("template")).unwrap();
+        fs::write(
+            template_dir.path().join("template/port.txt.die"),
+            "{{ port + 1 }}",
+        )
+        .unwrap();
+
+        let project_dir = tempfile::tempdir().unwrap();
+        fs::write(
+            project_dir.path().join(".diecut-answers.toml"),
+            "[_diecut]\ntemplate = \"typed\"\n\n[variables]\nport = 3000\nfeatures = [\"a,b\"]\n",
+        )
+        .unwrap();
+
+        let output_dir = tempfile::tempdir().unwrap();
+        let options = GenerateOptions {
+            template: Some(template_dir.path().display().to_string()),
+            output: Some(output_dir.path().display().to_string()),
+            answers_file: Some(project_dir.path().to_path_buf()),
+            no_hooks: true,
+            ..Default::default()
+        };
+
+        let plan = plan_generation(options).unwrap();
+
+        assert_eq!(
+            plan.variables.get("port").unwrap(),
+            &Value::Number(3000.into())
+        );
+        assert_eq!(
+            plan.variables.get("features").unwrap(),
+            &Value::Array(vec![Value::String("a,b".to_string())])
+        );
+        assert_eq!(plan.render_plan.files[0].content, b"3001");
+    }
+
+    #[rstest]
+    #[case("[_diecut]\ndiecut_version = \"0.3.6\"\n\n[variables]\n")]
+    // Generated from a local template: only the template's name is recorded.
+    #[case("[_diecut]\ntemplate = \"base\"\ndiecut_version = \"0.3.6\"\n\n[variables]\n")]
+    fn test_plan_generation_answers_file_without_source_errors(#[case] answers: &str) {
+        let project_dir = tempfile::tempdir().unwrap();
+        fs::write(project_dir.path().join(".diecut-answers.toml"), answers).unwrap();
+
+        let options = GenerateOptions {
+            answers_file: Some(project_dir.path().join(".diecut-answers.toml")),
+            no_hooks: true,
+            ..Default::default()
+        };
+
+        let result = plan_generation(options);
+        assert!(matches!(result, Err(DicecutError::MissingTemplateSource)));
+    }
+
+    #[test]
+    fn test_resolve_template_source_uses_user_abbreviations() {
+        let options = GenerateOptions {
+            template: Some("company:team/service@v1".to_string()),
+            abbreviations: HashMap::from([(
+                "company".to_string(),
+                "https://git.company.com/{}.git".to_string(),
+            )]),
+            ..Default::default()
+        };
+
+        match resolve_template_source(&options, None).unwrap() {
+            TemplateSource::Git { url, git_ref, .. } => {
+                assert_eq!(url, "https://git.company.com/team/service.git");
+                assert_eq!(git_ref.as_deref(), Some("v1"));
+            }
+            TemplateSource::Local(_) => panic!("expected a git source"),
+        }
+    }
+
     #[test]
     fn test_generate_end_to_end() {
         let template_dir = tempfile::tempdir().unwrap();
@@ -401,13 +730,12 @@ default = "test"
         let output_dir = tempfile::tempdir().unwrap();
 
         let options = GenerateOptions {
-            template: template_dir.path().display().to_string(),
+            template: Some(template_dir.path().display().to_string()),
             output: Some(output_dir.path().display().to_string()),
             data: vec![("project_name".to_string(), "my-proj".to_string())],
-            defaults: false,
             overwrite: true,
             no_hooks: true,
-            protocol: GitProtocol::default(),
+            ..Default::default()
         };
 
         let result = generate(options).unwrap();
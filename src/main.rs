@@ -3,22 +3,95 @@ mod commands;
 
 use clap::Parser;
 use cli::{Cli, Commands};
+use diecut::config::load_user_config;
 
 fn main() -> miette::Result<()> {
-    match Cli::parse().command {
+    let command = Cli::parse().command;
+
+    // `diecut config` must keep working when the config file itself is broken.
+    if let Commands::Config { command } = command {
+        return commands::config::run(command);
+    }
+    let user_config = load_user_config()?.unwrap_or_default();
+
+    match command {
         Commands::New {
             template,
+            git_ref,
+            format,
             output,
+            answers_file,
             data,
+            data_file,
             defaults,
+            no_input,
             overwrite,
             no_hooks,
             dry_run,
             verbose,
             protocol,
         } => commands::new::run(
-            template, output, data, defaults, overwrite, no_hooks, dry_run, verbose, protocol,
+            template,
+            git_ref,
+            format,
+            output,
+            answers_file,
+            data,
+            data_file,
+            defaults,
+            no_input,
+            overwrite,
+            no_hooks,
+            dry_run,
+            verbose,
+            protocol,
+            &user_config,
         ),
+        Commands::Replay {
+            project,
+            template,
+            output,
+            data,
+            overwrite,
+            no_hooks,
+            dry_run,
+            verbose,
+        } => {
+            // Without --output the project is regenerated in place, which
+            // still needs --overwrite to write over its existing files.
+            commands::new::run(
+                template,
+                None,
+                None,
+                Some(output.unwrap_or_else(|| project.clone())),
+                Some(project),
+                data,
+                None,
+                true,
+                true,
+                overwrite,
+                no_hooks,
+                dry_run,
+                verbose,
+                None,
+                &user_config,
+            )
+        }
         Commands::List => commands::list::run(),
+        Commands::Cache { command } => commands::cache::run(command, &user_config),
+        Commands::Update {
+            path,
+            git_ref,
+            defaults,
+            no_input,
+        } => commands::update::run(path, git_ref, defaults, no_input),
+        Commands::Check { path, format } => commands::check::run(path, format),
+        Commands::Convert {
+            template,
+            output,
+            format,
+            overwrite,
+        } => commands::convert::run(template, output, format, overwrite),
+        Commands::Config { .. } => unreachable!("handled before loading the user config"),
     }
 }
@@ -1,16 +1,30 @@
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
+use console::style;
 use tera::Value;
 
+use crate::answers::tera_value_to_toml;
 use crate::config::schema::TemplateConfig;
-use crate::config::variable::{VariableConfig, VariableType};
+use crate::config::variable::{Choice, VariableConfig, VariableType};
 use crate::error::{DicecutError, Result};
+use crate::prompt::order::{evaluation_order, variable_dependencies};
 use crate::render::build_context;
 
 #[derive(Default)]
 pub struct PromptOptions {
     pub data_overrides: HashMap<String, String>,
+    /// `DIECUT_VAR_<name>` values keyed by `<name>`, parsed like `--data`.
+    pub env_values: HashMap<String, String>,
+    /// Typed values from `--data-file`.
+    pub file_values: HashMap<String, Value>,
+    /// Typed answers (e.g. from a previous generation) used without prompting.
+    pub answers: HashMap<String, Value>,
     pub use_defaults: bool,
+    /// Never prompt: use defaults, and report every variable still without a value.
+    pub no_input: bool,
+    /// Set while collecting a table's or group's fields: the variable they
+    /// belong to. Secret fields then read `DIECUT_SECRET_<VAR>_<FIELD>`.
+    pub fields_of: Option<String>,
 }
 
 pub fn collect_variables(
@@ -18,69 +32,351 @@ pub fn collect_variables(
     options: &PromptOptions,
 ) -> Result<BTreeMap<String, Value>> {
     let mut values: BTreeMap<String, Value> = BTreeMap::new();
+    let mut problems = Vec::new();
 
-    for (name, var) in &config.variables {
-        if var.computed.is_some() {
-            continue; // computed vars are handled in second pass
+    let mut unknown: Vec<(&String, &str)> = options
+        .data_overrides
+        .keys()
+        .map(|name| (name, "not a variable in this template"))
+        .chain(
+            options
+                .file_values
+                .keys()
+                .map(|name| (name, "data file: not a variable in this template")),
+        )
+        .filter(|(name, _)| !config.variables.contains_key(*name))
+        .collect();
+    unknown.sort();
+    for (name, message) in unknown {
+        problems.push(DicecutError::ValidationFailed {
+            name: name.clone(),
+            message: message.into(),
+        });
+    }
+
+    let mut missing = Vec::new();
+    let result = collect_into(config, options, &mut values, &mut problems, &mut missing);
+    if !missing.is_empty() {
+        problems.push(DicecutError::MissingValues {
+            missing: missing
+                .iter()
+                .map(|name| {
+                    let var = &config.variables[*name];
+                    format!(
+                        "  {}",
+                        describe_missing(name, var, options.fields_of.as_deref())
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+        });
+    }
+    // Later failures (a computed value fed a rejected answer, say) are
+    // symptoms; the collected problems are what the user needs to fix.
+    if !problems.is_empty() {
+        return Err(DicecutError::from_problems(problems));
+    }
+    result.map(|()| values)
+}
+
+fn collect_into<'a>(
+    config: &'a TemplateConfig,
+    options: &PromptOptions,
+    values: &mut BTreeMap<String, Value>,
+    problems: &mut Vec<DicecutError>,
+    missing: &mut Vec<&'a str>,
+) -> Result<()> {
+    // Missing variables and everything that depends on them, which can't be evaluated.
+    let mut unresolved: BTreeSet<&str> = BTreeSet::new();
+
+    for name in evaluation_order(config)? {
+        let var = &config.variables[name];
+
+        if !unresolved.is_empty()
+            && variable_dependencies(name, var, config)
+                .iter()
+                .any(|dep| unresolved.contains(dep.as_str()))
+        {
+            unresolved.insert(name);
+            continue;
         }
 
         if let Some(when_expr) = &var.when {
-            if !evaluate_when(name, when_expr, &values)? {
+            if !evaluate_when(name, when_expr, values)? {
                 continue; // condition is false, skip
             }
         }
 
-        if let Some(override_val) = options.data_overrides.get(name) {
-            let value = parse_override(override_val, var);
-            values.insert(name.clone(), value);
+        if var.computed.is_some() {
+            let value = evaluate_computed(name, var, values)?;
+            values.insert(name.to_string(), value);
+            continue;
+        }
+
+        let available;
+        let var = if var.choices_from.is_some()
+            || var.choices.iter().flatten().any(|c| c.when.is_some())
+        {
+            available = available_choices(name, var, values)?;
+            &available
+        } else {
+            var
+        };
+
+        if let Some((source, parsed, raw)) = supplied_value(name, var, options) {
+            let checked = match parsed.and_then(|value| validate_value(name, value, var)) {
+                Ok(value) => check_validator(name, var, values, &value)?.map(|()| value),
+                Err(message) => Err(message),
+            };
+            let value = checked.unwrap_or_else(|message| {
+                problems.push(DicecutError::ValidationFailed {
+                    name: name.to_string(),
+                    message: match source {
+                        Some(source) => format!("{source}: {message}"),
+                        None => message,
+                    },
+                });
+                raw
+            });
+            values.insert(name.to_string(), value);
+            continue;
+        }
+
+        if let Some(answer) = options.answers.get(name) {
+            let value = if has_secret_fields(var) {
+                // Secret fields are never saved: ask for them again, then fill
+                // in and check the answer like any other supplied table.
+                let answer = if options.no_input {
+                    answer.clone()
+                } else {
+                    prompt_missing_secret_fields(name, var, answer.clone(), options.use_defaults)?
+                };
+                validate_value(name, answer.clone(), var).unwrap_or_else(|message| {
+                    problems.push(DicecutError::ValidationFailed {
+                        name: name.to_string(),
+                        message: format!("saved answer: {message}"),
+                    });
+                    answer
+                })
+            } else {
+                answer.clone()
+            };
+            values.insert(name.to_string(), value);
             continue;
         }
 
-        if options.use_defaults {
-            if let Some(default) = &var.default {
-                values.insert(name.clone(), toml_to_tera_value(default));
+        let default = resolve_default(name, var, values)?.or_else(|| empty_default(var));
+
+        if options.use_defaults || options.no_input {
+            if let Some(default) = &default {
+                let checked = match validate_value(name, toml_to_tera_value(default), var) {
+                    Ok(value) => check_validator(name, var, values, &value)?.map(|()| value),
+                    Err(message) => Err(message),
+                };
+                let value = checked.unwrap_or_else(|message| {
+                    problems.push(DicecutError::ValidationFailed {
+                        name: name.to_string(),
+                        message: format!("default {default}: {message}"),
+                    });
+                    toml_to_tera_value(default)
+                });
+                values.insert(name.to_string(), value);
                 continue;
             }
         }
 
-        let value = prompt_variable(name, var)?;
-        values.insert(name.clone(), value);
+        if options.no_input {
+            missing.push(name);
+            unresolved.insert(name);
+            continue;
+        }
+
+        // Don't ask for more input when the run is already going to fail.
+        if !problems.is_empty() {
+            return Ok(());
+        }
+
+        let rendered;
+        let prompted = if default == var.default {
+            var
+        } else {
+            rendered = VariableConfig {
+                default,
+                ..var.clone()
+            };
+            &rendered
+        };
+        let value = loop {
+            let value = prompt_variable(name, prompted)?;
+            match check_validator(name, var, values, &value)? {
+                Ok(()) => break value,
+                Err(message) => eprintln!("{} {message}", style("error:").red().bold()),
+            }
+        };
+        values.insert(name.to_string(), value);
     }
 
-    // Evaluate computed variables iteratively (they may depend on each other)
-    let computed_vars: Vec<_> = config
-        .variables
-        .iter()
-        .filter(|(_, v)| v.computed.is_some())
-        .map(|(name, var)| (name.clone(), var.computed.clone().unwrap()))
-        .collect();
+    Ok(())
+}
 
-    let mut remaining: Vec<(String, String)> = computed_vars;
-    let max_iterations = remaining.len() + 1;
-    for _ in 0..max_iterations {
-        if remaining.is_empty() {
-            break;
+/// A value given without prompting, with where it came from (`None` for `-d`),
+/// the parse result, and the raw value to keep going with if it's rejected.
+///
+/// Precedence: `-d`, then `DIECUT_VAR_<name>` (or `<NAME>`), then
+/// `DIECUT_SECRET_<NAME>` for secrets, then `--data-file`. A table's or group's
+/// secret fields come from the entry first, then `DIECUT_SECRET_<VAR>_<FIELD>`.
+fn supplied_value(
+    name: &str,
+    var: &VariableConfig,
+    options: &PromptOptions,
+) -> Option<(Option<String>, std::result::Result<Value, String>, Value)> {
+    let parsed = |raw: &String| parse_override(raw, var);
+    if let Some(raw) = options.data_overrides.get(name) {
+        return Some((None, parsed(raw), Value::String(raw.clone())));
+    }
+    for key in [name.to_string(), env_suffix(name)] {
+        if let Some(raw) = options.env_values.get(&key) {
+            let source = format!("{}{key}", crate::answers::data::VAR_ENV_PREFIX);
+            return Some((Some(source), parsed(raw), Value::String(raw.clone())));
         }
-        let mut still_pending = Vec::new();
-        for (name, computed_expr) in &remaining {
-            match evaluate_computed(name, computed_expr, &values) {
-                Ok(value) => {
-                    values.insert(name.clone(), value);
-                }
-                Err(_) => {
-                    still_pending.push((name.clone(), computed_expr.clone()));
-                }
+    }
+    let from_secret_env = || {
+        let key = var
+            .secret
+            .then(|| scoped_secret_env_var(name, options.fields_of.as_deref()))?;
+        let raw = std::env::var(&key).ok()?;
+        Some((Some(key), parsed(&raw), Value::String(raw)))
+    };
+    if options.fields_of.is_none() {
+        if let Some(supplied) = from_secret_env() {
+            return Some(supplied);
+        }
+    }
+    options
+        .file_values
+        .get(name)
+        .map(|value| {
+            (
+                Some("data file".to_string()),
+                Ok(value.clone()),
+                value.clone(),
+            )
+        })
+        .or_else(from_secret_env)
+}
+
+/// A variable name as it appears in environment variable names: upper-cased,
+/// with anything but letters and digits replaced by `_`.
+fn env_suffix(name: &str) -> String {
+    name.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+/// Environment variable that supplies a secret without prompting: `DIECUT_SECRET_<NAME>`.
+pub fn secret_env_var(name: &str) -> String {
+    format!("DIECUT_SECRET_{}", env_suffix(name))
+}
+
+/// [`secret_env_var`] for a variable, or `DIECUT_SECRET_<VAR>_<FIELD>` for a
+/// field of the table or group `fields_of`.
+fn scoped_secret_env_var(name: &str, fields_of: Option<&str>) -> String {
+    match fields_of {
+        Some(parent) => secret_env_var(&format!("{parent}_{name}")),
+        None => secret_env_var(name),
+    }
+}
+
+/// Split `-d KEY=VALUE` arguments, reporting every malformed one.
+pub fn parse_data_args(args: &[String]) -> Result<Vec<(String, String)>> {
+    let mut pairs = Vec::with_capacity(args.len());
+    let mut problems = Vec::new();
+    for arg in args {
+        match arg.split_once('=') {
+            Some((key, value)) if !key.trim().is_empty() => {
+                pairs.push((key.trim().to_string(), value.to_string()));
             }
+            _ => problems.push(DicecutError::ValidationFailed {
+                name: arg.clone(),
+                message: "expected KEY=VALUE".into(),
+            }),
         }
-        if still_pending.len() == remaining.len() {
-            // No progress — return the first error for diagnostics
-            let (name, expr) = &still_pending[0];
-            evaluate_computed(name, expr, &values)?;
+    }
+    if problems.is_empty() {
+        Ok(pairs)
+    } else {
+        Err(DicecutError::from_problems(problems))
+    }
+}
+
+/// One line of the `--no-input` report: the variable, its type, and how to supply it.
+fn describe_missing(name: &str, var: &VariableConfig, fields_of: Option<&str>) -> String {
+    let mut line = format!("{name} ({})", var.var_type.as_str());
+    if let Some(choices) = &var.choices {
+        let values: Vec<&str> = choices.iter().map(|c| c.value.as_str()).collect();
+        line.push_str(&format!(", one of: {}", values.join(", ")));
+    }
+    if var.secret {
+        line.push_str(&format!(
+            ", or set {}",
+            scoped_secret_env_var(name, fields_of)
+        ));
+    }
+    line
+}
+
+/// Run a non-interactive value through the checks a prompt would apply:
+/// type, `choices`, `validation`, and the range/length/item constraints.
+fn validate_value(
+    name: &str,
+    value: Value,
+    var: &VariableConfig,
+) -> std::result::Result<Value, String> {
+    let value = coerce_to_type(name, value, var)?;
+    if let (Some(pattern), Value::String(s)) = (&var.validation, &value) {
+        let re = regex_lite::Regex::new(pattern).map_err(|e| e.to_string())?;
+        if !re.is_match(s) {
+            return Err(var
+                .validation_message
+                .clone()
+                .unwrap_or_else(|| format!("must match pattern: {pattern}")));
         }
-        remaining = still_pending;
     }
+    var.check_constraints(&value)?;
+    Ok(value)
+}
 
-    Ok(values)
+/// Render a variable's `validator` with the answers so far and `value`.
+///
+/// The outer error is a broken template; the inner one is the rejection
+/// message, the validator's trimmed output.
+fn check_validator(
+    name: &str,
+    var: &VariableConfig,
+    values: &BTreeMap<String, Value>,
+    value: &Value,
+) -> Result<std::result::Result<(), String>> {
+    let Some(validator) = &var.validator else {
+        return Ok(Ok(()));
+    };
+    let mut context = build_context(values);
+    context.insert(name, value);
+    let output = tera::Tera::one_off(validator, &context, false).map_err(|e| {
+        DicecutError::ValidatorEvaluation {
+            name: name.to_string(),
+            source: e,
+        }
+    })?;
+    match output.trim() {
+        "" => Ok(Ok(())),
+        message => Ok(Err(message.to_string())),
+    }
 }
 
 fn evaluate_when(name: &str, when_expr: &str, values: &BTreeMap<String, Value>) -> Result<bool> {
@@ -91,34 +387,341 @@ fn evaluate_when(name: &str, when_expr: &str, values: &BTreeMap<String, Value>)
     })
 }
 
+/// Evaluate a computed variable into a value of its declared `type`.
 fn evaluate_computed(
     name: &str,
-    computed_expr: &str,
+    var: &VariableConfig,
     values: &BTreeMap<String, Value>,
 ) -> Result<Value> {
-    let mut tera = tera::Tera::default();
-    tera.add_raw_template("__computed__", computed_expr)
-        .map_err(|e| DicecutError::ComputedEvaluation {
+    let computed_expr = var.computed.as_deref().unwrap_or_default();
+    evaluate_typed(name, computed_expr, var, values).map_err(|source| {
+        DicecutError::ComputedEvaluation {
             name: name.to_string(),
-            source: e,
-        })?;
+            source,
+        }
+    })
+}
 
-    let context = build_context(values);
+/// What a list, table, or group without a `default` starts from: no entries,
+/// or a table made of its fields' own defaults.
+fn empty_default(var: &VariableConfig) -> Option<toml::Value> {
+    match var.var_type {
+        VariableType::List | VariableType::Group => Some(toml::Value::Array(Vec::new())),
+        VariableType::Table => Some(toml::Value::Table(toml::Table::new())),
+        _ => None,
+    }
+}
 
-    let result =
-        tera.render("__computed__", &context)
-            .map_err(|e| DicecutError::ComputedEvaluation {
+/// A copy of a select/multiselect variable whose `choices` are the ones on offer
+/// now: built from `choices_from`, minus choices whose `when` is false.
+fn available_choices(
+    name: &str,
+    var: &VariableConfig,
+    values: &BTreeMap<String, Value>,
+) -> Result<VariableConfig> {
+    let choices = match &var.choices_from {
+        Some(template) => evaluate_choices(template, values).map_err(|source| {
+            DicecutError::ChoicesEvaluation {
                 name: name.to_string(),
-                source: e,
-            })?;
+                source,
+            }
+        })?,
+        None => var.choices.clone().unwrap_or_default(),
+    };
+
+    let mut available = Vec::with_capacity(choices.len());
+    for mut choice in choices {
+        if let Some(when) = choice.when.take() {
+            if !evaluate_when(&format!("{name}.{}", choice.value), &when, values)? {
+                continue;
+            }
+        }
+        available.push(choice);
+    }
+    if available.is_empty() {
+        return Err(DicecutError::ValidationFailed {
+            name: name.to_string(),
+            message: "none of its choices are available".into(),
+        });
+    }
+
+    Ok(VariableConfig {
+        choices: Some(available),
+        choices_from: None,
+        ..var.clone()
+    })
+}
+
+/// Evaluate `choices_from` into choices: a list of strings, or of tables with
+/// `value` and optional `label` and `help`.
+fn evaluate_choices(template: &str, values: &BTreeMap<String, Value>) -> tera::Result<Vec<Choice>> {
+    let context = build_context(values);
+    let value = match single_expression(template) {
+        Some(expr) => evaluate_native(expr, &context)?,
+        None => {
+            let mut tera = tera::Tera::default();
+            tera.add_raw_template("__choices__", template)?;
+            let rendered = tera.render("__choices__", &context)?;
+            serde_json::from_str(&rendered).map_err(|_| {
+                tera::Error::msg(format!("expected a list of choices, got '{rendered}'"))
+            })?
+        }
+    };
+    let Value::Array(items) = value else {
+        return Err(tera::Error::msg(format!(
+            "expected a list of choices, got {value}"
+        )));
+    };
+
+    let text = |item: &Value, key: &str| -> tera::Result<Option<String>> {
+        match item.get(key) {
+            None | Some(Value::Null) => Ok(None),
+            Some(Value::String(s)) => Ok(Some(s.clone())),
+            Some(other) => Err(tera::Error::msg(format!(
+                "choice {key} must be a string, got {other}"
+            ))),
+        }
+    };
+    items
+        .into_iter()
+        .map(|item| match &item {
+            Value::String(s) => Ok(Choice::from(s.as_str())),
+            Value::Number(_) | Value::Bool(_) => Ok(Choice::from(item.to_string())),
+            Value::Object(_) => Ok(Choice {
+                value: text(&item, "value")?
+                    .ok_or_else(|| tera::Error::msg(format!("choice {item} has no value")))?,
+                label: text(&item, "label")?,
+                help: text(&item, "help")?,
+                when: None,
+            }),
+            other => Err(tera::Error::msg(format!("invalid choice {other}"))),
+        })
+        .collect()
+}
+
+/// The variable's default, with a templated string default (`"{{ author }}@example.com"`)
+/// rendered against the answers so far and converted to the variable's `type`.
+fn resolve_default(
+    name: &str,
+    var: &VariableConfig,
+    values: &BTreeMap<String, Value>,
+) -> Result<Option<toml::Value>> {
+    let Some(template) = var.templated_default() else {
+        return Ok(var.default.clone());
+    };
+    let value = evaluate_typed(name, template, var, values).map_err(|source| {
+        DicecutError::DefaultEvaluation {
+            name: name.to_string(),
+            source,
+        }
+    })?;
+    Ok(tera_value_to_toml(&value))
+}
+
+/// Render a Tera template into a value of the variable's declared `type`.
+///
+/// A template made of a single `{{ expression }}` is evaluated to a native
+/// Tera value (so `{{ count > 1 }}` is a real bool); anything else is rendered
+/// as text and parsed. Strings always use the rendered text.
+fn evaluate_typed(
+    name: &str,
+    template: &str,
+    var: &VariableConfig,
+    values: &BTreeMap<String, Value>,
+) -> tera::Result<Value> {
+    let context = build_context(values);
+
+    let value = match single_expression(template) {
+        Some(expr) if var.var_type != VariableType::String => evaluate_native(expr, &context)?,
+        _ => {
+            let mut tera = tera::Tera::default();
+            tera.add_raw_template("__computed__", template)?;
+            Value::String(tera.render("__computed__", &context)?)
+        }
+    };
+
+    coerce_to_type(name, value, var).map_err(tera::Error::msg)
+}
+
+/// The expression inside a template that is exactly one `{{ ... }}` block.
+fn single_expression(template: &str) -> Option<&str> {
+    let inner = template
+        .trim()
+        .strip_prefix("{{")?
+        .strip_suffix("}}")?
+        .trim_start_matches('-')
+        .trim_end_matches('-');
+    if inner.contains("{{") || inner.contains("}}") || inner.contains("{%") {
+        return None;
+    }
+    Some(inner.trim())
+}
+
+fn evaluate_native(expr: &str, context: &tera::Context) -> tera::Result<Value> {
+    let mut tera = tera::Tera::default();
+    tera.add_raw_template(
+        "__computed__",
+        &format!("{{% set __computed = {expr} %}}{{{{ __computed | json_encode() }}}}"),
+    )?;
+    let json = tera.render("__computed__", context)?;
+    serde_json::from_str(&json).map_err(|e| tera::Error::msg(e.to_string()))
+}
+
+/// Convert a computed, default, or `--data` value to the variable's declared type.
+fn coerce_to_type(
+    name: &str,
+    value: Value,
+    var: &VariableConfig,
+) -> std::result::Result<Value, String> {
+    let mismatch = |expected: &str, value: &Value| format!("expected {expected}, got {value}");
+    let check_choice = |choice: &str| {
+        if var.choices.is_some() && !var.has_choice(choice) {
+            Err(format!("'{choice}' is not one of the choices"))
+        } else {
+            Ok(())
+        }
+    };
+
+    match var.var_type {
+        VariableType::String => match value {
+            Value::String(_) => Ok(value),
+            other => Ok(Value::String(other.to_string())),
+        },
+        VariableType::Bool => match &value {
+            Value::Bool(_) => Ok(value),
+            Value::String(s) if s.trim() == "true" => Ok(Value::Bool(true)),
+            Value::String(s) if s.trim() == "false" => Ok(Value::Bool(false)),
+            _ => Err(mismatch("a bool (true or false)", &value)),
+        },
+        VariableType::Int => match &value {
+            Value::Number(n) if n.is_i64() => Ok(value),
+            Value::String(s) => s
+                .trim()
+                .parse::<i64>()
+                .map(Value::from)
+                .map_err(|_| mismatch("an integer", &value)),
+            _ => Err(mismatch("an integer", &value)),
+        },
+        VariableType::Float => {
+            let parsed = match &value {
+                Value::Number(n) => n.as_f64(),
+                Value::String(s) => s.trim().parse::<f64>().ok(),
+                _ => None,
+            };
+            match parsed {
+                Some(f) if f.is_finite() => Ok(Value::from(f)),
+                _ => Err(mismatch("a finite number", &value)),
+            }
+        }
+        VariableType::Select => match &value {
+            Value::String(s) => check_choice(s).map(|_| value.clone()),
+            _ => Err(mismatch("a string", &value)),
+        },
+        VariableType::Multiselect | VariableType::List => {
+            let items = match from_json(value) {
+                Value::Array(items) => items,
+                other => return Err(mismatch("a list", &other)),
+            };
+            for item in &items {
+                match item {
+                    Value::String(s) => check_choice(s)?,
+                    other => return Err(mismatch("a list of strings", other)),
+                }
+            }
+            Ok(Value::Array(items))
+        }
+        VariableType::Table => match from_json(value) {
+            Value::Object(entry) => collect_fields(name, var, entry),
+            other => Err(mismatch("a table", &other)),
+        },
+        VariableType::Group => match from_json(value) {
+            Value::Array(entries) => entries
+                .into_iter()
+                .enumerate()
+                .map(|(i, entry)| match entry {
+                    Value::Object(entry) => collect_fields(name, var, entry)
+                        .map_err(|e| format!("entry {}: {e}", i + 1)),
+                    other => Err(mismatch("a list of tables", &other)),
+                })
+                .collect::<std::result::Result<Vec<_>, _>>()
+                .map(Value::Array),
+            other => Err(mismatch("a list of tables", &other)),
+        },
+    }
+}
 
-    Ok(Value::String(result))
+/// A string holding a JSON list or object (from `-d` or rendered text), parsed;
+/// anything else unchanged.
+fn from_json(value: Value) -> Value {
+    match &value {
+        Value::String(s) if s.trim_start().starts_with(['[', '{']) => {
+            serde_json::from_str(s).unwrap_or(value)
+        }
+        _ => value,
+    }
+}
+
+/// Fill in and check one table (or group entry) against the variable's `fields`.
+///
+/// The fields are collected like top-level variables, with `entry` as their
+/// values: missing fields take their defaults, computed fields are evaluated,
+/// and every problem is reported as `field: message`.
+fn collect_fields(
+    name: &str,
+    var: &VariableConfig,
+    entry: serde_json::Map<String, Value>,
+) -> std::result::Result<Value, String> {
+    let options = PromptOptions {
+        file_values: entry.into_iter().collect(),
+        no_input: true,
+        fields_of: Some(name.to_string()),
+        ..Default::default()
+    };
+    let describe = |problem: &DicecutError| match problem {
+        DicecutError::ValidationFailed { name, message } => {
+            let message = message.strip_prefix("data file: ").unwrap_or(message);
+            format!("{name}: {message}")
+        }
+        DicecutError::MissingValues { missing } => {
+            let names: Vec<String> = missing
+                .lines()
+                .filter_map(|line| {
+                    let name = line.split_whitespace().next()?;
+                    Some(match line.split_once(", or set ") {
+                        Some((_, env)) => format!("{name} (or set {env})"),
+                        None => name.to_string(),
+                    })
+                })
+                .collect();
+            format!("missing {}", names.join(", "))
+        }
+        other => other.to_string(),
+    };
+    match collect_variables(&fields_config(var), &options) {
+        Ok(values) => Ok(Value::Object(values.into_iter().collect())),
+        Err(DicecutError::InvalidValues { problems }) => {
+            Err(problems.iter().map(describe).collect::<Vec<_>>().join("; "))
+        }
+        Err(problem) => Err(describe(&problem)),
+    }
+}
+
+/// A config whose variables are a table's or group's `fields`.
+fn fields_config(var: &VariableConfig) -> TemplateConfig {
+    TemplateConfig {
+        variables: var.fields.clone().unwrap_or_default(),
+        ..Default::default()
+    }
 }
 
 fn prompt_variable(name: &str, var: &VariableConfig) -> Result<Value> {
     let prompt_text = var.prompt.as_deref().unwrap_or(name);
 
     match var.var_type {
+        VariableType::String if var.secret => prompt_secret(name, prompt_text, var),
+        VariableType::List => prompt_list(prompt_text, var),
+        VariableType::Table => prompt_table(name, name, var, default_entry(var)),
+        VariableType::Group => prompt_group(name, prompt_text, var),
         VariableType::String => {
             let mut prompt = inquire::Text::new(prompt_text);
             if let Some(toml::Value::String(default)) = &var.default {
@@ -142,6 +745,10 @@ fn prompt_variable(name: &str, var: &VariableConfig) -> Result<Value> {
                     }
                 });
             }
+            let constraints = var.clone();
+            prompt = prompt.with_validator(move |input: &str| {
+                Ok(constraint_validation(constraints.check_length(input)))
+            });
             let answer = prompt.prompt().map_err(|_| DicecutError::PromptCancelled)?;
             Ok(Value::String(answer))
         }
@@ -163,16 +770,12 @@ fn prompt_variable(name: &str, var: &VariableConfig) -> Result<Value> {
                 default_str = n.to_string();
                 prompt = prompt.with_default(&default_str);
             }
-            prompt = prompt.with_validator(|input: &str| {
-                if input.parse::<i64>().is_ok() {
-                    Ok(inquire::validator::Validation::Valid)
-                } else {
-                    Ok(inquire::validator::Validation::Invalid(
-                        inquire::validator::ErrorMessage::Custom(
-                            "Must be a valid integer".to_string(),
-                        ),
-                    ))
-                }
+            let constraints = var.clone();
+            prompt = prompt.with_validator(move |input: &str| match input.parse::<i64>() {
+                Ok(n) => Ok(constraint_validation(constraints.check_range(n as f64))),
+                Err(_) => Ok(inquire::validator::Validation::Invalid(
+                    inquire::validator::ErrorMessage::Custom("Must be a valid integer".to_string()),
+                )),
             });
             let answer = prompt.prompt().map_err(|_| DicecutError::PromptCancelled)?;
             let n: i64 = answer.parse().map_err(|_| DicecutError::ValidationFailed {
@@ -188,8 +791,9 @@ fn prompt_variable(name: &str, var: &VariableConfig) -> Result<Value> {
                 default_str = f.to_string();
                 prompt = prompt.with_default(&default_str);
             }
-            prompt = prompt.with_validator(|input: &str| match input.parse::<f64>() {
-                Ok(f) if f.is_finite() => Ok(inquire::validator::Validation::Valid),
+            let constraints = var.clone();
+            prompt = prompt.with_validator(move |input: &str| match input.parse::<f64>() {
+                Ok(f) if f.is_finite() => Ok(constraint_validation(constraints.check_range(f))),
                 _ => Ok(inquire::validator::Validation::Invalid(
                     inquire::validator::ErrorMessage::Custom("Must be a finite number".to_string()),
                 )),
@@ -210,12 +814,12 @@ fn prompt_variable(name: &str, var: &VariableConfig) -> Result<Value> {
             let choices = var.choices.as_ref().expect("select must have choices");
             let mut prompt = inquire::Select::new(prompt_text, choices.clone());
             if let Some(toml::Value::String(default)) = &var.default {
-                if let Some(idx) = choices.iter().position(|c| c == default) {
+                if let Some(idx) = choices.iter().position(|c| &c.value == default) {
                     prompt = prompt.with_starting_cursor(idx);
                 }
             }
             let answer = prompt.prompt().map_err(|_| DicecutError::PromptCancelled)?;
-            Ok(Value::String(answer))
+            Ok(Value::String(answer.value))
         }
         VariableType::Multiselect => {
             let choices = var.choices.as_ref().expect("multiselect must have choices");
@@ -226,7 +830,7 @@ fn prompt_variable(name: &str, var: &VariableConfig) -> Result<Value> {
                         .iter()
                         .filter_map(|v| {
                             if let toml::Value::String(s) = v {
-                                choices.iter().position(|c| c == s)
+                                choices.iter().position(|c| &c.value == s)
                             } else {
                                 None
                             }
@@ -238,37 +842,273 @@ fn prompt_variable(name: &str, var: &VariableConfig) -> Result<Value> {
             if !default_indices.is_empty() {
                 prompt = prompt.with_default(&default_indices);
             }
+            let constraints = var.clone();
+            prompt = prompt.with_validator(
+                move |selected: &[inquire::list_option::ListOption<&Choice>]| {
+                    Ok(constraint_validation(
+                        constraints.check_items(selected.len()),
+                    ))
+                },
+            );
             let answers = prompt.prompt().map_err(|_| DicecutError::PromptCancelled)?;
-            let arr: Vec<Value> = answers.into_iter().map(Value::String).collect();
+            let arr: Vec<Value> = answers
+                .into_iter()
+                .map(|choice| Value::String(choice.value))
+                .collect();
             Ok(Value::Array(arr))
         }
     }
 }
 
-fn parse_override(value: &str, var: &VariableConfig) -> Value {
+/// Ask for list entries one at a time until an empty entry (or `max_items`).
+/// A non-empty default can be kept as a whole.
+fn prompt_list(prompt_text: &str, var: &VariableConfig) -> Result<Value> {
+    let defaults = match &var.default {
+        Some(toml::Value::Array(items)) if !items.is_empty() => Some(items),
+        _ => None,
+    };
+    if let Some(items) = defaults {
+        let shown: Vec<String> = items
+            .iter()
+            .map(|item| item.as_str().map_or_else(|| item.to_string(), String::from))
+            .collect();
+        if confirm(&format!("{prompt_text}: keep {}?", shown.join(", ")), true)? {
+            return Ok(toml_to_tera_value(&toml::Value::Array(items.clone())));
+        }
+    }
+
+    let min = var.min_items.unwrap_or(0);
+    let max = var.max_items.unwrap_or(usize::MAX);
+    let mut entries = Vec::new();
+    while entries.len() < max {
+        let label = format!("{prompt_text} #{}", entries.len() + 1);
+        let required = entries.len() < min;
+        let entry = inquire::Text::new(&label)
+            .with_help_message(if required {
+                "Required"
+            } else {
+                "Leave empty to finish"
+            })
+            .with_validator(move |input: &str| {
+                Ok(constraint_validation(if required && input.is_empty() {
+                    Err(format!("enter at least {min} entries"))
+                } else {
+                    Ok(())
+                }))
+            })
+            .prompt()
+            .map_err(|_| DicecutError::PromptCancelled)?;
+        if entry.is_empty() {
+            break;
+        }
+        entries.push(Value::String(entry));
+    }
+    Ok(Value::Array(entries))
+}
+
+/// Prompt for each of a table's fields, shown as `label.field`. Values in
+/// `defaults` replace the fields' own defaults.
+fn prompt_table(
+    name: &str,
+    label: &str,
+    var: &VariableConfig,
+    defaults: Option<&toml::Table>,
+) -> Result<Value> {
+    let mut config = fields_config(var);
+    for (field_name, field) in config.variables.iter_mut() {
+        if let Some(default) = defaults.and_then(|d| d.get(field_name)) {
+            field.default = Some(default.clone());
+        }
+        if field.prompt.is_none() && field.computed.is_none() {
+            field.prompt = Some(format!("{label}.{field_name}"));
+        }
+    }
+    let options = PromptOptions {
+        fields_of: Some(name.to_string()),
+        ..Default::default()
+    };
+    let values = collect_variables(&config, &options)?;
+    Ok(Value::Object(values.into_iter().collect()))
+}
+
+/// Ask for group entries until the user declines another (respecting
+/// `min_items`/`max_items`). A non-empty default can be kept as a whole.
+fn prompt_group(name: &str, prompt_text: &str, var: &VariableConfig) -> Result<Value> {
+    if let Some(toml::Value::Array(entries)) = &var.default {
+        if !entries.is_empty()
+            && confirm(
+                &format!("{prompt_text}: keep the {} default entries?", entries.len()),
+                true,
+            )?
+        {
+            return Ok(toml_to_tera_value(&toml::Value::Array(entries.clone())));
+        }
+    }
+
+    let min = var.min_items.unwrap_or(0);
+    let max = var.max_items.unwrap_or(usize::MAX);
+    let mut entries = Vec::new();
+    while entries.len() < max {
+        let number = entries.len() + 1;
+        if entries.len() >= min && !confirm(&format!("{prompt_text}: add entry #{number}?"), false)?
+        {
+            break;
+        }
+        entries.push(prompt_table(name, &format!("{name}[{number}]"), var, None)?);
+    }
+    Ok(Value::Array(entries))
+}
+
+/// Whether a table or group has secret fields, which saved answers lack.
+fn has_secret_fields(var: &VariableConfig) -> bool {
+    var.fields.iter().flatten().any(|(_, field)| field.secret)
+}
+
+/// Ask for the secret fields missing from a saved table or group answer,
+/// except those `DIECUT_SECRET_<VAR>_<FIELD>` supplies, hidden by `when`, or
+/// (with `use_defaults`) that have a default.
+fn prompt_missing_secret_fields(
+    name: &str,
+    var: &VariableConfig,
+    answer: Value,
+    use_defaults: bool,
+) -> Result<Value> {
+    let fill = |label: &str, mut entry: serde_json::Map<String, Value>| -> Result<Value> {
+        for (field_name, field) in var.fields.iter().flatten() {
+            if !field.secret
+                || field.computed.is_some()
+                || entry.contains_key(field_name)
+                || std::env::var(scoped_secret_env_var(field_name, Some(name))).is_ok()
+                || (use_defaults && field.default.is_some())
+            {
+                continue;
+            }
+            if let Some(when_expr) = &field.when {
+                let values: BTreeMap<String, Value> = entry.clone().into_iter().collect();
+                if !evaluate_when(field_name, when_expr, &values)? {
+                    continue;
+                }
+            }
+            let prompt_text = field
+                .prompt
+                .clone()
+                .unwrap_or_else(|| format!("{label}.{field_name}"));
+            let value = prompt_secret(field_name, &prompt_text, field)?;
+            entry.insert(field_name.clone(), value);
+        }
+        Ok(Value::Object(entry))
+    };
+    match answer {
+        Value::Object(entry) => fill(name, entry),
+        Value::Array(entries) => entries
+            .into_iter()
+            .enumerate()
+            .map(|(i, entry)| match entry {
+                Value::Object(entry) => fill(&format!("{name}[{}]", i + 1), entry),
+                other => Ok(other),
+            })
+            .collect::<Result<Vec<_>>>()
+            .map(Value::Array),
+        other => Ok(other),
+    }
+}
+
+/// A table variable's default, whose values pre-fill its fields.
+fn default_entry(var: &VariableConfig) -> Option<&toml::Table> {
+    match &var.default {
+        Some(toml::Value::Table(table)) => Some(table),
+        _ => None,
+    }
+}
+
+fn confirm(message: &str, default: bool) -> Result<bool> {
+    inquire::Confirm::new(message)
+        .with_default(default)
+        .prompt()
+        .map_err(|_| DicecutError::PromptCancelled)
+}
+
+/// Masked prompt for a secret string. An empty entry falls back to the default, if any.
+fn prompt_secret(name: &str, prompt_text: &str, var: &VariableConfig) -> Result<Value> {
+    let mut prompt = inquire::Password::new(prompt_text)
+        .with_display_mode(inquire::PasswordDisplayMode::Masked)
+        .with_custom_confirmation_message("Confirm:")
+        .with_custom_confirmation_error_message("The entries don't match.");
+    if !var.confirm {
+        prompt = prompt.without_confirmation();
+    }
+    let default = match &var.default {
+        Some(toml::Value::String(default)) => Some(default.clone()),
+        _ => None,
+    };
+    let checks = var.clone();
+    let name = name.to_string();
+    let has_default = default.is_some();
+    prompt = prompt.with_validator(move |input: &str| {
+        if input.is_empty() && has_default {
+            return Ok(inquire::validator::Validation::Valid);
+        }
+        Ok(constraint_validation(
+            validate_value(&name, Value::String(input.to_string()), &checks).map(|_| ()),
+        ))
+    });
+    if has_default {
+        prompt = prompt.with_help_message("Leave empty to use the default");
+    }
+
+    let answer = prompt.prompt().map_err(|_| DicecutError::PromptCancelled)?;
+    match default {
+        Some(default) if answer.is_empty() => Ok(Value::String(default)),
+        _ => Ok(Value::String(answer)),
+    }
+}
+
+fn constraint_validation(
+    result: std::result::Result<(), String>,
+) -> inquire::validator::Validation {
+    match result {
+        Ok(()) => inquire::validator::Validation::Valid,
+        Err(message) => inquire::validator::Validation::Invalid(
+            inquire::validator::ErrorMessage::Custom(capitalize(&message)),
+        ),
+    }
+}
+
+fn capitalize(message: &str) -> String {
+    let mut chars = message.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Turn a `-d key=value` string into a value for [`validate_value`].
+///
+/// Bools accept `true/false`, `yes/no`, `on/off` and `1/0`; multiselect values
+/// are comma-separated. Everything else is parsed by the type check.
+fn parse_override(value: &str, var: &VariableConfig) -> std::result::Result<Value, String> {
     match var.var_type {
-        VariableType::Bool => Value::Bool(value == "true" || value == "1" || value == "yes"),
-        VariableType::Int => value
-            .parse::<i64>()
-            .map(|n| Value::Number(serde_json::Number::from(n)))
-            .unwrap_or(Value::String(value.to_string())),
-        VariableType::Float => value
-            .parse::<f64>()
-            .ok()
-            .and_then(|f| serde_json::to_value(f).ok())
-            .unwrap_or(Value::String(value.to_string())),
-        VariableType::Multiselect => {
-            let items: Vec<Value> = value
+        VariableType::Bool => match value.to_ascii_lowercase().as_str() {
+            "true" | "yes" | "on" | "1" => Ok(Value::Bool(true)),
+            "false" | "no" | "off" | "0" => Ok(Value::Bool(false)),
+            _ => Err(format!("expected true or false, got '{value}'")),
+        },
+        VariableType::Multiselect | VariableType::List => Ok(Value::Array(
+            value
                 .split(',')
-                .map(|s| Value::String(s.trim().to_string()))
-                .collect();
-            Value::Array(items)
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| Value::String(s.to_string()))
+                .collect(),
+        )),
+        VariableType::Table | VariableType::Group => {
+            serde_json::from_str(value).map_err(|e| format!("expected JSON, {e}"))
         }
-        _ => Value::String(value.to_string()),
+        _ => Ok(Value::String(value.to_string())),
     }
 }
 
-fn toml_to_tera_value(val: &toml::Value) -> Value {
+pub(crate) fn toml_to_tera_value(val: &toml::Value) -> Value {
     match val {
         toml::Value::String(s) => Value::String(s.clone()),
         toml::Value::Integer(n) => Value::Number(serde_json::Number::from(*n)),
@@ -301,6 +1141,9 @@ mod tests {
                 version: None,
                 description: None,
                 min_diecut_version: None,
+                max_diecut_version: None,
+                deprecated: None,
+                extends: None,
                 templates_suffix: None,
             },
             variables,
@@ -326,7 +1169,7 @@ mod tests {
             VariableConfig {
                 var_type: VariableType::Select,
                 default: Some(toml::Value::String("MIT".to_string())),
-                choices: Some(vec!["MIT".to_string(), "Apache-2.0".to_string()]),
+                choices: Some(vec!["MIT".into(), "Apache-2.0".into()]),
                 ..Default::default()
             },
         );
@@ -343,6 +1186,7 @@ mod tests {
         let options = PromptOptions {
             data_overrides: HashMap::new(),
             use_defaults: true,
+            ..Default::default()
         };
 
         let result = collect_variables(&config, &options).unwrap();
@@ -377,6 +1221,7 @@ mod tests {
         let options = PromptOptions {
             data_overrides: overrides,
             use_defaults: false,
+            ..Default::default()
         };
 
         let result = collect_variables(&config, &options).unwrap();
@@ -415,6 +1260,7 @@ mod tests {
         let options = PromptOptions {
             data_overrides: HashMap::new(),
             use_defaults: true,
+            ..Default::default()
         };
 
         let result = collect_variables(&config, &options).unwrap();
@@ -460,6 +1306,7 @@ mod tests {
         let options = PromptOptions {
             data_overrides: HashMap::new(),
             use_defaults: true,
+            ..Default::default()
         };
 
         let result = collect_variables(&config, &options).unwrap();
@@ -493,6 +1340,997 @@ mod tests {
         assert_eq!(result, serde_json::Value::Null);
     }
 
+    fn computed(var_type: VariableType, expr: &str) -> VariableConfig {
+        VariableConfig {
+            var_type,
+            computed: Some(expr.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case(VariableType::Bool, "{{ ci_provider != 'none' }}", Value::Bool(false))]
+    #[case(VariableType::Bool, "{{- count > 1 -}}", Value::Bool(true))]
+    #[case(
+        VariableType::Bool,
+        "{% if count > 1 %}true{% else %}false{% endif %}",
+        Value::Bool(true)
+    )]
+    #[case(VariableType::Int, "{{ count * 2 }}", Value::from(6))]
+    #[case(VariableType::Int, "{{ count }}0", Value::from(30))]
+    #[case(VariableType::Float, "{{ count / 2 }}", Value::from(1.5))]
+    #[case(VariableType::String, "{{ count }}", Value::String("3".into()))]
+    #[case(VariableType::Multiselect, "{{ ['a', ci_provider] }}", serde_json::json!(["a", "none"]))]
+    fn test_computed_respects_type(
+        #[case] var_type: VariableType,
+        #[case] expr: &str,
+        #[case] expected: Value,
+    ) {
+        let values = BTreeMap::from([
+            ("ci_provider".to_string(), Value::String("none".into())),
+            ("count".to_string(), Value::from(3)),
+        ]);
+        let result = evaluate_computed("x", &computed(var_type, expr), &values).unwrap();
+        assert_eq!(result, expected);
+    }
+
+    #[rstest]
+    #[case(VariableType::Bool, "{{ name }}")]
+    #[case(VariableType::Int, "{{ name }}")]
+    #[case(VariableType::Int, "{{ 1.5 }}")]
+    #[case(VariableType::Float, "{{ name }}")]
+    fn test_computed_type_mismatch_errors(#[case] var_type: VariableType, #[case] expr: &str) {
+        let values = BTreeMap::from([("name".to_string(), Value::String("demo".into()))]);
+        let result = evaluate_computed("x", &computed(var_type, expr), &values);
+        match result {
+            Err(DicecutError::ComputedEvaluation { name, source }) => {
+                assert_eq!(name, "x");
+                assert!(source.to_string().contains("expected"), "{source}");
+            }
+            other => panic!("expected ComputedEvaluation error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_computed_select_must_be_a_choice() {
+        let var = VariableConfig {
+            choices: Some(vec!["mit".into()]),
+            ..computed(VariableType::Select, "{{ name }}")
+        };
+        let values = BTreeMap::from([("name".to_string(), Value::String("gpl".into()))]);
+        assert!(evaluate_computed("x", &var, &values).is_err());
+    }
+
+    /// A computed bool that is false must be falsy in `when` conditions.
+    #[test]
+    fn test_computed_bool_drives_when() {
+        let mut variables = IndexMap::new();
+        variables.insert(
+            "ci_provider".to_string(),
+            VariableConfig {
+                default: Some(toml::Value::String("none".into())),
+                ..Default::default()
+            },
+        );
+        variables.insert(
+            "use_ci".to_string(),
+            computed(VariableType::Bool, "{{ ci_provider != 'none' }}"),
+        );
+
+        let config = minimal_config(variables);
+        let values = collect_variables(
+            &config,
+            &PromptOptions {
+                use_defaults: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(values["use_ci"], Value::Bool(false));
+        let context = build_context(&values);
+        assert!(!crate::render::eval_bool_expr("use_ci", &context).unwrap());
+    }
+
+    /// A prompted variable declared first can still depend on a computed one.
+    #[test]
+    fn test_when_can_depend_on_later_computed() {
+        let mut variables = IndexMap::new();
+        variables.insert(
+            "docker_image".to_string(),
+            VariableConfig {
+                default: Some(toml::Value::String("python:3.12".into())),
+                when: Some("use_ci".to_string()),
+                ..Default::default()
+            },
+        );
+        variables.insert(
+            "use_ci".to_string(),
+            computed(VariableType::Bool, "{{ ci_provider != 'none' }}"),
+        );
+        variables.insert(
+            "ci_provider".to_string(),
+            VariableConfig {
+                default: Some(toml::Value::String("github".into())),
+                ..Default::default()
+            },
+        );
+
+        let values = collect_variables(
+            &minimal_config(variables),
+            &PromptOptions {
+                use_defaults: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(values["use_ci"], Value::Bool(true));
+        assert_eq!(values["docker_image"], "python:3.12");
+    }
+
+    fn with_default(var_type: VariableType, default: &str) -> VariableConfig {
+        VariableConfig {
+            var_type,
+            default: Some(toml::Value::String(default.to_string())),
+            ..Default::default()
+        }
+    }
+
+    /// Templated defaults render against earlier answers, whatever order they are declared in.
+    #[test]
+    fn test_templated_defaults_use_earlier_answers() {
+        let mut variables = IndexMap::new();
+        variables.insert(
+            "crate_name".to_string(),
+            with_default(VariableType::String, "{{ project_name | slugify }}"),
+        );
+        variables.insert(
+            "project_name".to_string(),
+            with_default(VariableType::String, "My App"),
+        );
+        variables.insert(
+            "email".to_string(),
+            with_default(VariableType::String, "{{ author | lower }}@example.com"),
+        );
+        variables.insert(
+            "author".to_string(),
+            with_default(VariableType::String, "Jane"),
+        );
+        variables.insert(
+            "base_port".to_string(),
+            VariableConfig {
+                var_type: VariableType::Int,
+                default: Some(toml::Value::Integer(8080)),
+                ..Default::default()
+            },
+        );
+        variables.insert(
+            "admin_port".to_string(),
+            with_default(VariableType::Int, "{{ base_port + 1 }}"),
+        );
+
+        let values = collect_variables(
+            &minimal_config(variables),
+            &PromptOptions {
+                use_defaults: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(values["crate_name"], "my-app");
+        assert_eq!(values["email"], "jane@example.com");
+        assert_eq!(values["admin_port"], Value::from(8081));
+    }
+
+    /// An explicit answer replaces the templated default without rendering it.
+    #[test]
+    fn test_override_skips_templated_default() {
+        let mut variables = IndexMap::new();
+        variables.insert(
+            "crate_name".to_string(),
+            with_default(VariableType::String, "{{ missing }}"),
+        );
+
+        let values = collect_variables(
+            &minimal_config(variables),
+            &PromptOptions {
+                data_overrides: HashMap::from([("crate_name".to_string(), "mine".to_string())]),
+                use_defaults: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        assert_eq!(values["crate_name"], "mine");
+    }
+
+    #[test]
+    fn test_templated_default_error_names_variable() {
+        let mut variables = IndexMap::new();
+        variables.insert(
+            "port".to_string(),
+            with_default(VariableType::Int, "{{ undefined_var }}"),
+        );
+
+        let result = collect_variables(
+            &minimal_config(variables),
+            &PromptOptions {
+                use_defaults: true,
+                ..Default::default()
+            },
+        );
+        match result {
+            Err(DicecutError::DefaultEvaluation { name, .. }) => assert_eq!(name, "port"),
+            other => panic!("expected a default evaluation error, got {other:?}"),
+        }
+    }
+
+    fn constrained(var_type: VariableType) -> VariableConfig {
+        VariableConfig {
+            var_type,
+            choices: Some(vec!["a".into(), "b".into(), "c".into()]),
+            min: Some(1.0),
+            max: Some(10.0),
+            min_length: Some(2),
+            max_length: Some(4),
+            min_items: Some(1),
+            max_items: Some(2),
+            ..Default::default()
+        }
+    }
+
+    #[rstest]
+    #[case(VariableType::Int, "5", None)]
+    #[case(VariableType::Int, "0", Some("must be at least 1"))]
+    #[case(VariableType::Float, "10.5", Some("must be at most 10"))]
+    #[case(VariableType::String, "abc", None)]
+    #[case(VariableType::String, "a", Some("must be at least 2 characters"))]
+    #[case(VariableType::String, "abcde", Some("must be at most 4 characters"))]
+    #[case(VariableType::Multiselect, "a,b", None)]
+    #[case(
+        VariableType::Multiselect,
+        "a,b,c",
+        Some("must select at most 2 items")
+    )]
+    fn test_overrides_respect_constraints(
+        #[case] var_type: VariableType,
+        #[case] input: &str,
+        #[case] expected_error: Option<&str>,
+    ) {
+        let mut variables = IndexMap::new();
+        variables.insert("v".to_string(), constrained(var_type));
+
+        let result = collect_variables(
+            &minimal_config(variables),
+            &PromptOptions {
+                data_overrides: HashMap::from([("v".to_string(), input.to_string())]),
+                ..Default::default()
+            },
+        );
+        match (result, expected_error) {
+            (Ok(_), None) => {}
+            (Err(DicecutError::ValidationFailed { name, message }), Some(expected)) => {
+                assert_eq!(name, "v");
+                assert_eq!(message, expected);
+            }
+            (other, _) => panic!("unexpected result for {input:?}: {other:?}"),
+        }
+    }
+
+    /// A templated default that renders outside the bounds is rejected under `--defaults`.
+    #[test]
+    fn test_rendered_default_respects_constraints() {
+        let mut variables = IndexMap::new();
+        variables.insert(
+            "workers".to_string(),
+            VariableConfig {
+                max: Some(8.0),
+                ..with_default(VariableType::Int, "{{ 4 * 4 }}")
+            },
+        );
+
+        let result = collect_variables(
+            &minimal_config(variables),
+            &PromptOptions {
+                use_defaults: true,
+                ..Default::default()
+            },
+        );
+        assert!(matches!(
+            result,
+            Err(DicecutError::ValidationFailed { ref name, .. }) if name == "workers"
+        ));
+    }
+
+    #[rstest]
+    #[case("yes", Some(true))]
+    #[case("On", Some(true))]
+    #[case("0", Some(false))]
+    #[case("FALSE", Some(false))]
+    #[case("maybe", None)]
+    fn test_parse_override_bool(#[case] input: &str, #[case] expected: Option<bool>) {
+        let var = VariableConfig {
+            var_type: VariableType::Bool,
+            ..Default::default()
+        };
+        assert_eq!(parse_override(input, &var).ok(), expected.map(Value::Bool));
+    }
+
+    fn license_and_port() -> TemplateConfig {
+        let mut variables = IndexMap::new();
+        variables.insert(
+            "license".to_string(),
+            VariableConfig {
+                var_type: VariableType::Select,
+                choices: Some(vec!["MIT".into(), "Apache-2.0".into()]),
+                default: Some(toml::Value::String("MIT".into())),
+                ..Default::default()
+            },
+        );
+        variables.insert(
+            "port".to_string(),
+            VariableConfig {
+                var_type: VariableType::Int,
+                default: Some(toml::Value::Integer(8080)),
+                ..Default::default()
+            },
+        );
+        variables.insert(
+            "slug".to_string(),
+            VariableConfig {
+                default: Some(toml::Value::String("app".into())),
+                validation: Some("^[a-z]+$".into()),
+                ..Default::default()
+            },
+        );
+        minimal_config(variables)
+    }
+
+    #[test]
+    fn test_invalid_overrides_are_reported_together() {
+        let options = PromptOptions {
+            data_overrides: HashMap::from([
+                ("license".to_string(), "MTI".to_string()),
+                ("port".to_string(), "80a".to_string()),
+                ("slug".to_string(), "Not A Slug".to_string()),
+                ("licence".to_string(), "MIT".to_string()),
+            ]),
+            use_defaults: true,
+            ..Default::default()
+        };
+
+        let problems = match collect_variables(&license_and_port(), &options) {
+            Err(DicecutError::InvalidValues { problems }) => problems,
+            other => panic!("expected every problem at once, got {other:?}"),
+        };
+        let reported: Vec<(String, String)> = problems
+            .into_iter()
+            .map(|p| match p {
+                DicecutError::ValidationFailed { name, message } => (name, message),
+                other => panic!("expected ValidationFailed, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            reported,
+            vec![
+                (
+                    "licence".to_string(),
+                    "not a variable in this template".to_string()
+                ),
+                (
+                    "license".to_string(),
+                    "'MTI' is not one of the choices".to_string()
+                ),
+                (
+                    "port".to_string(),
+                    "expected an integer, got \"80a\"".to_string()
+                ),
+                (
+                    "slug".to_string(),
+                    "must match pattern: ^[a-z]+$".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_valid_overrides_are_typed() {
+        let options = PromptOptions {
+            data_overrides: HashMap::from([
+                ("license".to_string(), "Apache-2.0".to_string()),
+                ("port".to_string(), "3000".to_string()),
+            ]),
+            use_defaults: true,
+            ..Default::default()
+        };
+        let values = collect_variables(&license_and_port(), &options).unwrap();
+        assert_eq!(values["license"], "Apache-2.0");
+        assert_eq!(values["port"], Value::from(3000));
+        assert_eq!(values["slug"], "app");
+    }
+
+    #[test]
+    fn test_defaults_are_validated() {
+        let mut config = license_and_port();
+        config.variables["license"].default = Some(toml::Value::String("GPL".into()));
+
+        let result = collect_variables(
+            &config,
+            &PromptOptions {
+                use_defaults: true,
+                ..Default::default()
+            },
+        );
+        match result {
+            Err(DicecutError::ValidationFailed { name, message }) => {
+                assert_eq!(name, "license");
+                assert_eq!(message, "default \"GPL\": 'GPL' is not one of the choices");
+            }
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    }
+
+    #[rstest]
+    #[case("8443", None)]
+    #[case("80", Some("Port 80 must be above 1024 unless slug is root"))]
+    fn test_validator_sees_value_and_earlier_answers(
+        #[case] port: &str,
+        #[case] expected: Option<&str>,
+    ) {
+        let mut config = license_and_port();
+        config.variables["port"].validator = Some(
+            "{% if port <= 1024 and slug != 'root' %}\n  Port {{ port }} must be above 1024 unless slug is root\n{% endif %}"
+                .into(),
+        );
+        config.variables.move_index(2, 1);
+
+        let result = collect_variables(
+            &config,
+            &PromptOptions {
+                data_overrides: HashMap::from([("port".to_string(), port.to_string())]),
+                use_defaults: true,
+                ..Default::default()
+            },
+        );
+        match (result, expected) {
+            (Ok(values), None) => assert_eq!(values["port"], 8443),
+            (Err(DicecutError::ValidationFailed { name, message }), Some(expected)) => {
+                assert_eq!(name, "port");
+                assert_eq!(message, expected);
+            }
+            (other, _) => panic!("unexpected result {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_data_args_reports_malformed() {
+        let args = vec![
+            "name=demo".to_string(),
+            "url=https://x?a=b".to_string(),
+            "broken".to_string(),
+            "=value".to_string(),
+        ];
+        match parse_data_args(&args) {
+            Err(DicecutError::InvalidValues { problems }) => assert_eq!(problems.len(), 2),
+            other => panic!("expected malformed arguments, got {other:?}"),
+        }
+        assert_eq!(
+            parse_data_args(&args[..2]).unwrap(),
+            vec![
+                ("name".to_string(), "demo".to_string()),
+                ("url".to_string(), "https://x?a=b".to_string()),
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("api_token", "DIECUT_SECRET_API_TOKEN")]
+    #[case("db-password", "DIECUT_SECRET_DB_PASSWORD")]
+    fn test_secret_env_var(#[case] name: &str, #[case] expected: &str) {
+        assert_eq!(secret_env_var(name), expected);
+    }
+
+    /// `--no-input` reports every variable without a value in one error, skipping
+    /// those hidden by `when` and those that only depend on a missing one.
+    #[test]
+    fn test_no_input_lists_all_missing_variables() {
+        let mut variables = IndexMap::new();
+        variables.insert("project_name".to_string(), VariableConfig::default());
+        variables.insert(
+            "use_ci".to_string(),
+            VariableConfig {
+                var_type: VariableType::Bool,
+                default: Some(toml::Value::Boolean(false)),
+                ..Default::default()
+            },
+        );
+        variables.insert(
+            "ci_token".to_string(),
+            VariableConfig {
+                when: Some("use_ci".to_string()),
+                ..Default::default()
+            },
+        );
+        variables.insert(
+            "license".to_string(),
+            VariableConfig {
+                var_type: VariableType::Select,
+                choices: Some(vec!["MIT".into(), "Apache-2.0".into()]),
+                ..Default::default()
+            },
+        );
+        variables.insert(
+            "api_token".to_string(),
+            VariableConfig {
+                secret: true,
+                ..Default::default()
+            },
+        );
+        variables.insert(
+            "crate_name".to_string(),
+            with_default(VariableType::String, "{{ project_name | slugify }}"),
+        );
+        variables.insert(
+            "slug".to_string(),
+            computed(VariableType::String, "{{ crate_name }}"),
+        );
+
+        let result = collect_variables(
+            &minimal_config(variables),
+            &PromptOptions {
+                no_input: true,
+                ..Default::default()
+            },
+        );
+        match result {
+            Err(DicecutError::MissingValues { missing }) => assert_eq!(
+                missing,
+                "  project_name (string)\n  \
+                 license (select), one of: MIT, Apache-2.0\n  \
+                 api_token (string), or set DIECUT_SECRET_API_TOKEN"
+            ),
+            other => panic!("expected missing values, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_no_input_uses_defaults_and_overrides() {
+        let mut variables = IndexMap::new();
+        variables.insert("project_name".to_string(), VariableConfig::default());
+        variables.insert(
+            "crate_name".to_string(),
+            with_default(VariableType::String, "{{ project_name | slugify }}"),
+        );
+
+        let values = collect_variables(
+            &minimal_config(variables),
+            &PromptOptions {
+                data_overrides: HashMap::from([("project_name".into(), "My App".into())]),
+                no_input: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(values["crate_name"], "my-app");
+    }
+
+    /// `-d` beats `DIECUT_VAR_<name>`, which beats `--data-file`, which beats defaults.
+    #[test]
+    fn test_value_source_precedence() {
+        let mut variables = IndexMap::new();
+        for name in ["from_flag", "from_env", "from_file", "from_default"] {
+            variables.insert(
+                name.to_string(),
+                with_default(VariableType::String, "default"),
+            );
+        }
+        variables.insert(
+            "features".to_string(),
+            VariableConfig {
+                var_type: VariableType::Multiselect,
+                choices: Some(vec!["api".into(), "cli".into(), "web".into()]),
+                ..Default::default()
+            },
+        );
+        variables.insert(
+            "use_ci".to_string(),
+            VariableConfig {
+                var_type: VariableType::Bool,
+                ..Default::default()
+            },
+        );
+
+        let options = PromptOptions {
+            data_overrides: HashMap::from([("from_flag".into(), "flag".into())]),
+            env_values: HashMap::from([
+                ("from_flag".into(), "env".into()),
+                ("from_env".into(), "env".into()),
+                ("USE_CI".into(), "yes".into()),
+            ]),
+            file_values: HashMap::from([
+                ("from_flag".into(), Value::from("file")),
+                ("from_env".into(), Value::from("file")),
+                ("from_file".into(), Value::from("file")),
+                ("features".into(), serde_json::json!(["api", "web"])),
+            ]),
+            use_defaults: true,
+            ..Default::default()
+        };
+        let values = collect_variables(&minimal_config(variables), &options).unwrap();
+
+        assert_eq!(values["from_flag"], "flag");
+        assert_eq!(values["from_env"], "env");
+        assert_eq!(values["from_file"], "file");
+        assert_eq!(values["from_default"], "default");
+        assert_eq!(values["features"], serde_json::json!(["api", "web"]));
+        assert_eq!(values["use_ci"], Value::Bool(true));
+    }
+
+    #[test]
+    fn test_data_file_values_are_validated() {
+        let options = PromptOptions {
+            file_values: HashMap::from([
+                ("license".into(), Value::from("GPL")),
+                ("port".into(), Value::from("8081")),
+                ("owner".into(), Value::from("core")),
+            ]),
+            use_defaults: true,
+            ..Default::default()
+        };
+
+        let problems = match collect_variables(&license_and_port(), &options) {
+            Err(DicecutError::InvalidValues { problems }) => problems,
+            other => panic!("expected invalid values, got {other:?}"),
+        };
+        let messages: Vec<String> = problems.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Validation failed for variable 'owner': data file: not a variable in this template",
+                "Validation failed for variable 'license': data file: 'GPL' is not one of the choices",
+            ]
+        );
+    }
+
+    fn choice_config(toml_src: &str) -> TemplateConfig {
+        let config: TemplateConfig =
+            toml::from_str(&format!("[template]\nname = \"choices\"\n{toml_src}")).unwrap();
+        config.validate().unwrap();
+        config
+    }
+
+    #[test]
+    fn test_choice_tables_store_the_value() {
+        let config = choice_config(
+            r#"
+[variables.license]
+type = "select"
+default = "mit"
+choices = [
+    { value = "mit", label = "MIT License", help = "Permissive" },
+    "unlicense",
+]
+"#,
+        );
+        let choices = config.variables["license"].choices.as_ref().unwrap();
+        assert_eq!(choices[0].to_string(), "MIT License - Permissive");
+        assert_eq!(choices[1].to_string(), "unlicense");
+
+        let values = collect_variables(
+            &config,
+            &PromptOptions {
+                use_defaults: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(values["license"], "mit");
+    }
+
+    #[rstest]
+    #[case("true", "aws", Ok("aws"))]
+    #[case("false", "aws", Err("'aws' is not one of the choices"))]
+    #[case("false", "local", Ok("local"))]
+    fn test_choice_when_hides_options(
+        #[case] cloud: &str,
+        #[case] target: &str,
+        #[case] expected: std::result::Result<&str, &str>,
+    ) {
+        let config = choice_config(
+            r#"
+[variables.cloud]
+type = "bool"
+
+[variables.target]
+type = "select"
+choices = [
+    { value = "aws", when = "cloud" },
+    "local",
+]
+"#,
+        );
+        let result = collect_variables(
+            &config,
+            &PromptOptions {
+                data_overrides: HashMap::from([
+                    ("cloud".into(), cloud.into()),
+                    ("target".into(), target.into()),
+                ]),
+                ..Default::default()
+            },
+        );
+        match (result, expected) {
+            (Ok(values), Ok(value)) => assert_eq!(values["target"], value),
+            (Err(DicecutError::ValidationFailed { message, .. }), Err(expected)) => {
+                assert_eq!(message, expected)
+            }
+            (other, _) => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_choices_from_earlier_answers() {
+        let config = choice_config(
+            r#"
+[variables.services]
+type = "multiselect"
+choices = ["api", "worker"]
+default = ["api", "worker"]
+
+[variables.entrypoint]
+type = "select"
+choices_from = "{{ services }}"
+default = "worker"
+
+[variables.region]
+type = "select"
+choices_from = "[{% for r in ['eu', 'us'] %}{\"value\": \"{{ r }}\", \"label\": \"{{ r | upper }}\"}{% if not loop.last %},{% endif %}{% endfor %}]"
+default = "us"
+"#,
+        );
+        let values = collect_variables(
+            &config,
+            &PromptOptions {
+                use_defaults: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(values["entrypoint"], "worker");
+        assert_eq!(values["region"], "us");
+
+        let result = collect_variables(
+            &config,
+            &PromptOptions {
+                data_overrides: HashMap::from([("services".into(), "api".into())]),
+                use_defaults: true,
+                ..Default::default()
+            },
+        );
+        assert!(matches!(
+            result,
+            Err(DicecutError::ValidationFailed { ref name, .. }) if name == "entrypoint"
+        ));
+    }
+
+    #[test]
+    fn test_choices_from_must_be_a_list() {
+        let config = choice_config(
+            r#"
+[variables.pick]
+type = "select"
+choices_from = "{{ 42 }}"
+"#,
+        );
+        let result = collect_variables(
+            &config,
+            &PromptOptions {
+                no_input: true,
+                ..Default::default()
+            },
+        );
+        assert!(matches!(
+            result,
+            Err(DicecutError::ChoicesEvaluation { ref name, .. }) if name == "pick"
+        ));
+    }
+
+    fn services_config() -> TemplateConfig {
+        choice_config(
+            r#"
+[variables.tags]
+type = "list"
+
+[variables.owner]
+type = "table"
+
+[variables.owner.fields.team]
+type = "string"
+default = "platform"
+
+[variables.owner.fields.oncall]
+type = "bool"
+default = false
+
+[variables.services]
+type = "group"
+max_items = 3
+
+[variables.services.fields.name]
+type = "string"
+
+[variables.services.fields.port]
+type = "int"
+default = 8080
+min = 1
+
+[variables.services.fields.slug]
+type = "string"
+computed = "{{ name | slugify }}"
+"#,
+        )
+    }
+
+    /// Lists, tables, and groups arrive as Tera arrays and objects, with fields
+    /// filled from their defaults and computed from their siblings.
+    #[test]
+    fn test_structured_values_from_data() {
+        let options = PromptOptions {
+            data_overrides: HashMap::from([("tags".into(), "web, internal".into())]),
+            file_values: HashMap::from([(
+                "services".into(),
+                serde_json::json!([
+                    {"name": "Billing API"},
+                    {"name": "worker", "port": 9000},
+                ]),
+            )]),
+            no_input: true,
+            ..Default::default()
+        };
+        let values = collect_variables(&services_config(), &options).unwrap();
+
+        assert_eq!(values["tags"], serde_json::json!(["web", "internal"]));
+        assert_eq!(
+            values["owner"],
+            serde_json::json!({"team": "platform", "oncall": false})
+        );
+        assert_eq!(
+            values["services"],
+            serde_json::json!([
+                {"name": "Billing API", "port": 8080, "slug": "billing-api"},
+                {"name": "worker", "port": 9000, "slug": "worker"},
+            ])
+        );
+    }
+
+    #[test]
+    fn test_structured_defaults_without_entries() {
+        let values = collect_variables(
+            &services_config(),
+            &PromptOptions {
+                no_input: true,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        assert_eq!(values["tags"], serde_json::json!([]));
+        assert_eq!(values["services"], serde_json::json!([]));
+    }
+
+    #[rstest]
+    #[case(r#"[{"port": 0}]"#, "entry 1: port: must be at least 1; missing name")]
+    #[case(
+        r#"[{"name": "a", "extra": 1}]"#,
+        "entry 1: extra: not a variable in this template"
+    )]
+    #[case(r#"{"name": "a"}"#, r#"expected a list of tables, got {"name":"a"}"#)]
+    #[case(
+        r#"[{"name": "a"}, {"name": "b"}, {"name": "c"}, {"name": "d"}]"#,
+        "must have at most 3 items"
+    )]
+    fn test_group_entries_are_validated(#[case] json: &str, #[case] expected: &str) {
+        let result = collect_variables(
+            &services_config(),
+            &PromptOptions {
+                data_overrides: HashMap::from([("services".into(), json.into())]),
+                no_input: true,
+                ..Default::default()
+            },
+        );
+        match result {
+            Err(DicecutError::ValidationFailed { name, message }) => {
+                assert_eq!(name, "services");
+                assert_eq!(message, expected);
+            }
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+    }
+
+    fn databases_config() -> TemplateConfig {
+        let mut fields = IndexMap::new();
+        fields.insert("name".to_string(), VariableConfig::default());
+        fields.insert(
+            "password".to_string(),
+            VariableConfig {
+                secret: true,
+                ..Default::default()
+            },
+        );
+        let mut variables = IndexMap::new();
+        variables.insert(
+            "databases".to_string(),
+            VariableConfig {
+                var_type: VariableType::Group,
+                fields: Some(fields),
+                ..Default::default()
+            },
+        );
+        minimal_config(variables)
+    }
+
+    /// A secret field's value in the entry wins; `DIECUT_SECRET_<VAR>_<FIELD>`
+    /// only fills it in, and the unscoped `DIECUT_SECRET_<FIELD>` is ignored.
+    #[test]
+    #[serial_test::serial]
+    fn test_secret_fields_use_scoped_env() {
+        std::env::set_var("DIECUT_SECRET_PASSWORD", "unscoped");
+        std::env::set_var("DIECUT_SECRET_DATABASES_PASSWORD", "scoped");
+        let result = collect_variables(
+            &databases_config(),
+            &PromptOptions {
+                file_values: HashMap::from([(
+                    "databases".into(),
+                    serde_json::json!([{"name": "a", "password": "given"}, {"name": "b"}]),
+                )]),
+                no_input: true,
+                ..Default::default()
+            },
+        );
+        std::env::remove_var("DIECUT_SECRET_PASSWORD");
+        std::env::remove_var("DIECUT_SECRET_DATABASES_PASSWORD");
+
+        assert_eq!(
+            result.unwrap()["databases"],
+            serde_json::json!([
+                {"name": "a", "password": "given"},
+                {"name": "b", "password": "scoped"},
+            ])
+        );
+    }
+
+    /// Saved answers never hold secret fields, so reusing them asks for the
+    /// fields again, or reports how to supply them.
+    #[test]
+    #[serial_test::serial]
+    fn test_saved_answers_missing_secret_fields() {
+        let options = PromptOptions {
+            answers: HashMap::from([("databases".into(), serde_json::json!([{"name": "a"}]))]),
+            no_input: true,
+            ..Default::default()
+        };
+
+        match collect_variables(&databases_config(), &options) {
+            Err(DicecutError::ValidationFailed { name, message }) => {
+                assert_eq!(name, "databases");
+                assert_eq!(
+                    message,
+                    "saved answer: entry 1: missing password (or set DIECUT_SECRET_DATABASES_PASSWORD)"
+                );
+            }
+            other => panic!("expected a validation error, got {other:?}"),
+        }
+
+        std::env::set_var("DIECUT_SECRET_DATABASES_PASSWORD", "hunter2");
+        let result = collect_variables(&databases_config(), &options);
+        std::env::remove_var("DIECUT_SECRET_DATABASES_PASSWORD");
+        assert_eq!(
+            result.unwrap()["databases"],
+            serde_json::json!([{"name": "a", "password": "hunter2"}])
+        );
+    }
+
     /// Integration test: verify Tera errors in computed variables are caught and wrapped
     #[test]
     fn test_computed_variable_evaluation_error() {
@@ -509,6 +2347,7 @@ mod tests {
         let options = PromptOptions {
             data_overrides: HashMap::new(),
             use_defaults: true,
+            ..Default::default()
         };
 
         // Should error because undefined_var doesn't exist
@@ -1,3 +1,5 @@
 pub mod engine;
+pub mod order;
 
-pub use engine::{collect_variables, PromptOptions};
+pub use engine::{collect_variables, parse_data_args, PromptOptions};
+pub use order::evaluation_order;
@@ -0,0 +1,231 @@
+use std::collections::BTreeSet;
+
+use crate::config::schema::TemplateConfig;
+use crate::config::variable::VariableConfig;
+use crate::error::{DicecutError, Result};
+use crate::render::references::{expression_references, template_references};
+
+/// Variables a variable's `when`, `computed`, templated `default`, `validator`,
+/// and choices refer to.
+pub fn variable_dependencies(
+    name: &str,
+    var: &VariableConfig,
+    config: &TemplateConfig,
+) -> BTreeSet<String> {
+    let mut refs = BTreeSet::new();
+    if let Some(when) = &var.when {
+        refs.extend(expression_references(when));
+    }
+    if let Some(computed) = &var.computed {
+        refs.extend(template_references(computed));
+    }
+    if let Some(toml::Value::String(default)) = &var.default {
+        refs.extend(template_references(default));
+    }
+    if let Some(validator) = &var.validator {
+        refs.extend(template_references(validator));
+    }
+    if let Some(choices_from) = &var.choices_from {
+        refs.extend(template_references(choices_from));
+    }
+    for when in var.choices.iter().flatten().filter_map(|c| c.when.as_ref()) {
+        refs.extend(expression_references(when));
+    }
+    refs.retain(|r| r != name && config.variables.contains_key(r));
+    refs
+}
+
+/// Order variables so each one comes after the variables it depends on.
+///
+/// Among variables whose dependencies are satisfied, declaration order wins,
+/// so templates without cross-references are prompted exactly as declared.
+/// A dependency cycle is reported with the variables that form it.
+pub fn evaluation_order(config: &TemplateConfig) -> Result<Vec<&str>> {
+    let names: Vec<&str> = config.variables.keys().map(String::as_str).collect();
+    let deps: Vec<BTreeSet<String>> = config
+        .variables
+        .iter()
+        .map(|(name, var)| variable_dependencies(name, var, config))
+        .collect();
+
+    let mut order = Vec::with_capacity(names.len());
+    let mut done = vec![false; names.len()];
+    while order.len() < names.len() {
+        let ready = (0..names.len())
+            .find(|&i| !done[i] && deps[i].iter().all(|d| order.contains(&d.as_str())));
+        match ready {
+            Some(i) => {
+                done[i] = true;
+                order.push(names[i]);
+            }
+            None => {
+                return Err(DicecutError::VariableCycle {
+                    cycle: find_cycle(&names, &deps, &done).join(" -> "),
+                })
+            }
+        }
+    }
+    Ok(order)
+}
+
+/// Walk unresolved dependencies from the first pending variable until one repeats.
+fn find_cycle<'a>(names: &[&'a str], deps: &[BTreeSet<String>], done: &[bool]) -> Vec<&'a str> {
+    let index_of = |name: &str| names.iter().position(|n| *n == name);
+    let mut path: Vec<usize> = Vec::new();
+    let mut current = (0..names.len())
+        .find(|&i| !done[i])
+        .expect("a cycle leaves a pending variable");
+
+    loop {
+        if let Some(start) = path.iter().position(|&i| i == current) {
+            let mut cycle: Vec<&str> = path[start..].iter().map(|&i| names[i]).collect();
+            cycle.push(names[current]);
+            return cycle;
+        }
+        path.push(current);
+        current = deps[current]
+            .iter()
+            .filter_map(|d| index_of(d))
+            .find(|&i| !done[i])
+            .expect("a pending variable has a pending dependency");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::variable::Choice;
+    use indexmap::IndexMap;
+
+    fn config(vars: &[(&str, VariableConfig)]) -> TemplateConfig {
+        let variables: IndexMap<String, VariableConfig> = vars
+            .iter()
+            .map(|(name, var)| (name.to_string(), var.clone()))
+            .collect();
+        let mut config: TemplateConfig = toml::from_str("[template]\nname = \"order\"").unwrap();
+        config.variables = variables;
+        config
+    }
+
+    fn when(expr: &str) -> VariableConfig {
+        VariableConfig {
+            when: Some(expr.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn computed(expr: &str) -> VariableConfig {
+        VariableConfig {
+            computed: Some(expr.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn declaration_order_is_kept_without_dependencies() {
+        let config = config(&[
+            ("b", VariableConfig::default()),
+            ("a", VariableConfig::default()),
+            ("c", VariableConfig::default()),
+        ]);
+        assert_eq!(evaluation_order(&config).unwrap(), vec!["b", "a", "c"]);
+    }
+
+    #[test]
+    fn prompted_variable_can_depend_on_computed() {
+        let config = config(&[
+            ("docker_image", when("use_ci")),
+            ("use_ci", computed("{{ ci_provider != 'none' }}")),
+            ("ci_provider", VariableConfig::default()),
+        ]);
+        assert_eq!(
+            evaluation_order(&config).unwrap(),
+            vec!["ci_provider", "use_ci", "docker_image"]
+        );
+    }
+
+    #[test]
+    fn templated_default_is_a_dependency() {
+        let config = config(&[
+            (
+                "crate_name",
+                VariableConfig {
+                    default: Some(toml::Value::String(
+                        "{{ project_name | slugify }}".to_string(),
+                    )),
+                    ..Default::default()
+                },
+            ),
+            ("project_name", VariableConfig::default()),
+        ]);
+        assert_eq!(
+            evaluation_order(&config).unwrap(),
+            vec!["project_name", "crate_name"]
+        );
+    }
+
+    #[test]
+    fn choices_from_and_choice_when_are_dependencies() {
+        let config = config(&[
+            (
+                "target",
+                VariableConfig {
+                    choices: Some(vec![Choice {
+                        when: Some("cloud".into()),
+                        ..Choice::from("aws")
+                    }]),
+                    ..Default::default()
+                },
+            ),
+            (
+                "entrypoint",
+                VariableConfig {
+                    choices_from: Some("{{ services }}".into()),
+                    ..Default::default()
+                },
+            ),
+            ("services", VariableConfig::default()),
+            ("cloud", VariableConfig::default()),
+        ]);
+        assert_eq!(
+            evaluation_order(&config).unwrap(),
+            vec!["services", "entrypoint", "cloud", "target"]
+        );
+    }
+
+    #[test]
+    fn cycle_names_the_variables_involved() {
+        let config = config(&[
+            ("unrelated", VariableConfig::default()),
+            ("a", computed("{{ b }}")),
+            ("b", when("c")),
+            ("c", computed("{{ a }}-x")),
+        ]);
+        match evaluation_order(&config) {
+            Err(DicecutError::VariableCycle { cycle }) => assert_eq!(cycle, "a -> b -> c -> a"),
+            other => panic!("expected a cycle error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn self_reference_is_ignored() {
+        let config = config(&[("a", when("a is defined"))]);
+        assert_eq!(evaluation_order(&config).unwrap(), vec!["a"]);
+    }
+
+    #[test]
+    fn loop_variable_is_not_a_dependency() {
+        let config = config(&[
+            (
+                "names",
+                computed("{% for item in items %}{{ item }}{% endfor %}"),
+            ),
+            ("item", computed("{{ names }}")),
+            ("items", VariableConfig::default()),
+        ]);
+        assert_eq!(
+            evaluation_order(&config).unwrap(),
+            vec!["items", "names", "item"]
+        );
+    }
+}
@@ -13,11 +13,21 @@ pub fn render_file_content(tera: &Tera, template_name: &str, context: &Context)
 }
 
 /// Render template expressions in a path component (e.g. `{{project_name}}`).
-pub fn render_path_component(component: &str, context: &Context) -> Result<String> {
-    Tera::one_off(component, context, false).map_err(|e| DicecutError::FilenameRenderError {
-        filename: component.to_string(),
-        source: e,
-    })
+///
+/// `tera` holds the template's partials, so path components can use their macros.
+pub fn render_path_component(
+    tera: &mut Tera,
+    component: &str,
+    context: &Context,
+) -> Result<String> {
+    if !component.contains('{') {
+        return Ok(component.to_string());
+    }
+    tera.render_str(component, context)
+        .map_err(|e| DicecutError::FilenameRenderError {
+            filename: component.to_string(),
+            source: e,
+        })
 }
 
 /// Detect binary files using content_inspector (BOM-aware, null-byte scanning).
@@ -67,7 +77,8 @@ mod tests {
         let mut context = Context::new();
         context.insert("project_name", "my-project");
 
-        let result = render_path_component("{{project_name}}", &context).unwrap();
+        let result =
+            render_path_component(&mut Tera::default(), "{{project_name}}", &context).unwrap();
         assert_eq!(result, "my-project");
     }
 
@@ -75,7 +86,7 @@ mod tests {
     fn test_render_path_component_error() {
         let context = Context::new();
 
-        let result = render_path_component("{{invalid_var}}", &context);
+        let result = render_path_component(&mut Tera::default(), "{{invalid_var}}", &context);
         assert!(result.is_err());
         if let Err(err) = result {
             assert!(matches!(err, DicecutError::FilenameRenderError { .. }));
@@ -1,5 +1,7 @@
 pub mod context;
 pub mod file;
+pub mod partials;
+pub mod references;
 pub mod walker;
 
 pub use context::{build_context, eval_bool_expr};
@@ -0,0 +1,43 @@
+use globset::GlobSet;
+use tera::Tera;
+
+use crate::adapter::{tera_name, ContentFile, ResolvedTemplate};
+use crate::error::{DicecutError, Result};
+
+/// Build the Tera instance shared by every rendered file and path component.
+///
+/// It holds the files from `partials/` and the content files matching
+/// `files.partials`, so templates can `include`, `import`, and `extends` them.
+pub fn load_partials(
+    resolved: &ResolvedTemplate,
+    content_files: &[ContentFile],
+    partial_set: &GlobSet,
+) -> Result<Tera> {
+    let mut sources = Vec::new();
+    for (name, path) in resolved.partial_files() {
+        sources.push((name, read(&path)?));
+    }
+    for file in content_files {
+        if partial_set.is_match(&file.rel_path) {
+            sources.push((tera_name(&file.rel_path), read(&file.src_path)?));
+        }
+    }
+
+    let mut tera = Tera::default();
+    // Generated files aren't web pages: without a templates suffix (as in
+    // Cookiecutter templates) `.html` and `.xml` files would be HTML-escaped.
+    tera.autoescape_on(vec![]);
+    tera.add_raw_templates(sources)
+        .map_err(|e| DicecutError::RenderError {
+            file: "partials".to_string(),
+            source: e,
+        })?;
+    Ok(tera)
+}
+
+fn read(path: &std::path::Path) -> Result<String> {
+    std::fs::read_to_string(path).map_err(|e| DicecutError::Io {
+        context: format!("reading {}", path.display()),
+        source: e,
+    })
+}
@@ -0,0 +1,192 @@
+use std::collections::BTreeSet;
+
+/// Tera keywords, operators and literals that can never name a variable.
+const KEYWORDS: &[&str] = &[
+    "and",
+    "as",
+    "block",
+    "break",
+    "continue",
+    "elif",
+    "else",
+    "endblock",
+    "endfilter",
+    "endfor",
+    "endif",
+    "endmacro",
+    "endraw",
+    "extends",
+    "false",
+    "False",
+    "filter",
+    "for",
+    "if",
+    "ignore",
+    "import",
+    "in",
+    "include",
+    "is",
+    "loop",
+    "macro",
+    "missing",
+    "not",
+    "or",
+    "raw",
+    "self",
+    "set",
+    "set_global",
+    "super",
+    "true",
+    "True",
+];
+
+/// Top-level names referenced by a Tera template (file content, path, or `computed`).
+///
+/// Only the code inside `{{ }}` and `{% %}` is scanned; literal text and
+/// `{# #}` comments are ignored. Names bound by `{% for %}` (inside the loop)
+/// and `{% set %}` (after the tag) are local to the template and left out.
+pub fn template_references(source: &str) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    let mut loop_vars: Vec<Vec<String>> = Vec::new();
+    let mut set_vars: BTreeSet<String> = BTreeSet::new();
+    let mut rest = source;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        let close = match after.chars().next() {
+            Some('{') => "}}",
+            Some('%') => "%}",
+            Some('#') => "#}",
+            _ => {
+                rest = after;
+                continue;
+            }
+        };
+        let body = &after[1..];
+        let end = body.find(close).unwrap_or(body.len());
+        rest = &body[(end + close.len()).min(body.len())..];
+        if close == "#}" {
+            continue;
+        }
+
+        let code = body[..end].trim_matches(|c: char| c == '-' || c.is_whitespace());
+        let (keyword, args) = code.split_once(char::is_whitespace).unwrap_or((code, ""));
+        let (expr, bound) = match (close, keyword) {
+            ("%}", "for") => match args.split_once(" in ") {
+                Some((targets, iterable)) => (iterable, Some(targets)),
+                None => (args, None),
+            },
+            ("%}", "set" | "set_global") => match args.split_once('=') {
+                Some((target, value)) => (value, Some(target)),
+                None => (args, None),
+            },
+            ("%}", "endfor") => {
+                loop_vars.pop();
+                continue;
+            }
+            _ => (code, None),
+        };
+
+        names.extend(expression_references(expr).into_iter().filter(|name| {
+            !set_vars.contains(name) && !loop_vars.iter().flatten().any(|v| v == name)
+        }));
+        match (keyword, bound) {
+            ("for", Some(targets)) => {
+                loop_vars.push(targets.split(',').map(|t| t.trim().to_string()).collect());
+            }
+            (_, Some(target)) => {
+                set_vars.insert(target.trim().to_string());
+            }
+            _ => {}
+        }
+    }
+    names
+}
+
+/// Top-level names referenced by a bare Tera expression (a `when` condition).
+///
+/// Attribute accesses (`a.b` yields only `a`), filter and test names, string
+/// literals and keywords are skipped.
+pub fn expression_references(expr: &str) -> BTreeSet<String> {
+    let mut names = BTreeSet::new();
+    let chars: Vec<char> = expr.chars().collect();
+    let mut i = 0;
+    let mut prev_significant = ' ';
+    let mut prev_word = String::new();
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c == '"' || c == '\'' || c == '`' {
+            i += 1;
+            while i < chars.len() && chars[i] != c {
+                i += 1;
+            }
+            i += 1;
+            prev_significant = c;
+            continue;
+        }
+        if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            let is_attribute = prev_significant == '.';
+            let is_filter = prev_significant == '|';
+            let is_test =
+                prev_word == "is" || (prev_word == "not" && is_test_position(&chars, start));
+            let is_keyword = KEYWORDS.contains(&word.as_str());
+            if !is_attribute && !is_filter && !is_test && !is_keyword {
+                names.insert(word.clone());
+            }
+            prev_word = word;
+            prev_significant = 'a';
+            continue;
+        }
+        if !c.is_whitespace() {
+            prev_significant = c;
+            prev_word.clear();
+        }
+        i += 1;
+    }
+    names
+}
+
+/// Whether the word at `start` follows `is not` (a negated Tera test).
+fn is_test_position(chars: &[char], start: usize) -> bool {
+    let before: String = chars[..start].iter().collect();
+    before.trim_end().ends_with("is not")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rstest::rstest;
+
+    fn set(names: &[&str]) -> BTreeSet<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[rstest]
+    #[case("Hello {{ name }}!", &["name"])]
+    #[case("{{ project.name | upper }}", &["project"])]
+    #[case("{% if use_ci %}ci{% endif %}", &["use_ci"])]
+    #[case("{# author #}{{ 'license' }}", &[])]
+    #[case("{{ a ~ \"b\" }}{% for x in items %}{{ x }}{% endfor %}", &["a", "items"])]
+    #[case("{% for k, v in map %}{{ k }}={{ v.y }}{% endfor %}{{ v }}", &["map", "v"])]
+    #[case("{{ name }}{% set name = other | lower %}{{ name }}", &["name", "other"])]
+    #[case("{%- set_global total = 0 -%}{{ total + count }}", &["count"])]
+    #[case("plain { braces } only", &[])]
+    fn template_references_cases(#[case] source: &str, #[case] expected: &[&str]) {
+        assert_eq!(template_references(source), set(expected));
+    }
+
+    #[rstest]
+    #[case("use_docker and ci == 'github'", &["ci", "use_docker"])]
+    #[case("name is defined", &["name"])]
+    #[case("name is not defined", &["name"])]
+    #[case("not enabled or mode in [\"a\", \"b\"]", &["enabled", "mode"])]
+    #[case("count > 2", &["count"])]
+    fn expression_references_cases(#[case] expr: &str, #[case] expected: &[&str]) {
+        assert_eq!(expression_references(expr), set(expected));
+    }
+}
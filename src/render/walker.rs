@@ -1,15 +1,15 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use globset::{Glob, GlobSet, GlobSetBuilder};
 use tera::{Context, Tera, Value};
-use walkdir::WalkDir;
 
-use crate::adapter::ResolvedTemplate;
-use crate::config::schema::FilesConfig;
+use crate::adapter::{ContentFile, ResolvedTemplate};
+use crate::config::schema::{EachFile, FilesConfig};
 use crate::error::{DicecutError, Result};
 use crate::render::build_context;
 use crate::render::file::{is_binary_file, render_path_component};
+use crate::render::partials::load_partials;
 
 pub struct GeneratedProject {
     pub output_dir: PathBuf,
@@ -38,12 +38,7 @@ pub fn plan_render(
     variables: &BTreeMap<String, Value>,
     context: &Context,
 ) -> Result<GenerationPlan> {
-    let content_dir = &resolved.content_dir;
-    if !content_dir.exists() {
-        return Err(DicecutError::TemplateDirectoryMissing {
-            path: content_dir.clone(),
-        });
-    }
+    let content_files = resolved.content_files()?;
 
     let config = &resolved.config;
     let suffix = config
@@ -53,81 +48,171 @@ pub fn plan_render(
         .unwrap_or(crate::config::schema::DEFAULT_TEMPLATES_SUFFIX);
     let exclude_set = build_glob_set(&config.files.exclude)?;
     let copy_set = build_glob_set(&config.files.copy_without_render)?;
+    let partial_set = build_glob_set(&config.files.partials)?;
     let conditional_excludes = evaluate_conditional_files(&config.files, variables)?;
+    let each_rules = config
+        .files
+        .each
+        .iter()
+        .map(|rule| Ok((each_glob(&rule.pattern)?.compile_matcher(), rule)))
+        .collect::<Result<Vec<_>>>()?;
+    let mut planner = FilePlanner {
+        partials: load_partials(resolved, &content_files, &partial_set)?,
+        suffix,
+        skip_empty_paths: resolved.skip_empty_paths,
+        copy_set,
+        conditional_excludes,
+    };
 
     let mut files = Vec::new();
 
-    for entry in WalkDir::new(content_dir)
-        .min_depth(1)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let src_path = entry.path();
-        let rel_path = src_path
-            .strip_prefix(content_dir)
-            .expect("entry must be under content_dir");
-
+    for ContentFile { rel_path, src_path } in &content_files {
         let rel_str = rel_path.to_string_lossy();
 
-        if exclude_set.is_match(rel_str.as_ref()) {
+        if exclude_set.is_match(rel_str.as_ref()) || partial_set.is_match(rel_str.as_ref()) {
             continue;
         }
 
-        let rendered_rel = render_relative_path(rel_path, context, suffix)?;
-        let rendered_str = rendered_rel.to_string_lossy();
-
-        if conditional_excludes.is_match(rendered_str.as_ref()) {
-            continue;
+        let each_rule = each_rules
+            .iter()
+            .find(|(matcher, _)| matcher.is_match(rel_str.as_ref()))
+            .map(|(_, rule)| *rule);
+        match each_rule {
+            Some(rule) => {
+                let mut seen = HashSet::new();
+                for item_context in each_contexts(rule, variables, context)? {
+                    if let Some(file) = planner.plan_file(rel_path, src_path, &item_context)? {
+                        if !seen.insert(file.relative_path.clone()) {
+                            return Err(DicecutError::InvalidEachRule {
+                                pattern: rule.pattern.clone(),
+                                reason: format!(
+                                    "two elements of '{}' render to {}",
+                                    rule.over,
+                                    file.relative_path.display()
+                                ),
+                            });
+                        }
+                        files.push(file);
+                    }
+                }
+            }
+            None => files.extend(planner.plan_file(rel_path, src_path, context)?),
         }
+    }
 
-        if entry.file_type().is_dir() {
-            continue;
+    Ok(GenerationPlan { files })
+}
+
+/// Renders one source file into a [`PlannedFile`] for a given context.
+struct FilePlanner<'a> {
+    partials: Tera,
+    suffix: &'a str,
+    skip_empty_paths: bool,
+    copy_set: GlobSet,
+    conditional_excludes: GlobSet,
+}
+
+impl FilePlanner<'_> {
+    /// Plan a single output file, or `None` if a `[[files.conditional]]` rule
+    /// excludes it or (with `skip_empty_paths`) a path component renders empty.
+    fn plan_file(
+        &mut self,
+        rel_path: &Path,
+        src_path: &Path,
+        context: &Context,
+    ) -> Result<Option<PlannedFile>> {
+        let rel_str = rel_path.to_string_lossy();
+        let Some(rendered_rel) = render_relative_path(
+            &mut self.partials,
+            rel_path,
+            context,
+            self.suffix,
+            self.skip_empty_paths,
+        )?
+        else {
+            return Ok(None);
+        };
+        let rendered_str = rendered_rel.to_string_lossy();
+
+        if self.conditional_excludes.is_match(rendered_str.as_ref()) {
+            return Ok(None);
         }
 
-        let should_copy = copy_set.is_match(rendered_str.as_ref())
+        let should_copy = self.copy_set.is_match(rendered_str.as_ref())
             || is_binary_file(src_path)
-            || (!suffix.is_empty() && !src_path.to_string_lossy().ends_with(suffix));
+            || (!self.suffix.is_empty() && !src_path.to_string_lossy().ends_with(self.suffix));
 
         if should_copy {
             let content = std::fs::read(src_path).map_err(|e| DicecutError::Io {
                 context: format!("reading {}", src_path.display()),
                 source: e,
             })?;
-            files.push(PlannedFile {
+            return Ok(Some(PlannedFile {
                 relative_path: rendered_rel,
                 content,
                 is_copy: true,
-            });
-        } else {
-            let content = std::fs::read_to_string(src_path).map_err(|e| DicecutError::Io {
-                context: format!("reading {}", src_path.display()),
+            }));
+        }
+
+        let content = std::fs::read_to_string(src_path).map_err(|e| DicecutError::Io {
+            context: format!("reading {}", src_path.display()),
+            source: e,
+        })?;
+
+        let mut tera = self.partials.clone();
+        let template_name = rel_str.to_string();
+        tera.add_raw_template(&template_name, &content)
+            .and_then(|_| tera.render(&template_name, context))
+            .map(|rendered| {
+                Some(PlannedFile {
+                    relative_path: rendered_rel,
+                    content: rendered.into_bytes(),
+                    is_copy: false,
+                })
+            })
+            .map_err(|e| DicecutError::RenderError {
+                file: template_name,
                 source: e,
-            })?;
+            })
+    }
+}
 
-            let mut tera = Tera::default();
-            let template_name = rel_str.to_string();
-            let parse_result = tera.add_raw_template(&template_name, &content);
-            let render_result = parse_result.and_then(|_| tera.render(&template_name, context));
-
-            match render_result {
-                Ok(rendered) => {
-                    files.push(PlannedFile {
-                        relative_path: rendered_rel,
-                        content: rendered.into_bytes(),
-                        is_copy: false,
-                    });
-                }
-                Err(e) => {
-                    return Err(DicecutError::RenderError {
-                        file: rel_str.to_string(),
-                        source: e,
-                    });
-                }
-            }
+/// One context per element of the rule's `over` variable, with the element bound to `as`.
+///
+/// A variable skipped by its `when` condition yields no contexts, so no files.
+fn each_contexts(
+    rule: &EachFile,
+    variables: &BTreeMap<String, Value>,
+    context: &Context,
+) -> Result<Vec<Context>> {
+    let items = match variables.get(&rule.over) {
+        None | Some(Value::Null) => return Ok(Vec::new()),
+        Some(Value::Array(items)) => items,
+        Some(other) => {
+            return Err(DicecutError::InvalidEachRule {
+                pattern: rule.pattern.clone(),
+                reason: format!("'{}' is {other}, not a list", rule.over),
+            })
         }
-    }
+    };
+    Ok(items
+        .iter()
+        .map(|item| {
+            let mut item_context = context.clone();
+            item_context.insert(&rule.binding, item);
+            item_context
+        })
+        .collect())
+}
 
-    Ok(GenerationPlan { files })
+/// Compile a `[[files.each]]` pattern, treating `{` and `}` literally so
+/// Tera expressions in the path aren't read as glob alternations.
+pub(crate) fn each_glob(pattern: &str) -> Result<Glob> {
+    let escaped = pattern.replace('{', "[{]").replace('}', "[}]");
+    Glob::new(&escaped).map_err(|e| DicecutError::GlobPattern {
+        pattern: pattern.to_string(),
+        source: e,
+    })
 }
 
 /// Write the files from a generation plan to disk.
@@ -173,23 +258,35 @@ pub fn walk_and_render(
 }
 
 /// Render each component of a relative path through Tera, and strip the template suffix.
-fn render_relative_path(rel_path: &Path, context: &Context, suffix: &str) -> Result<PathBuf> {
+///
+/// With `skip_empty`, returns `None` if a component renders empty, so
+/// `{% if x %}name{% endif %}` works as a conditional file or directory name.
+fn render_relative_path(
+    tera: &mut Tera,
+    rel_path: &Path,
+    context: &Context,
+    suffix: &str,
+    skip_empty: bool,
+) -> Result<Option<PathBuf>> {
     let mut rendered = PathBuf::new();
     for component in rel_path.components() {
         let part = component.as_os_str().to_string_lossy();
-        let mut rendered_part = render_path_component(&part, context)?;
+        let mut rendered_part = render_path_component(tera, &part, context)?;
 
         // Strip template suffix from the final component (filename)
         if !suffix.is_empty() && rendered_part.ends_with(suffix) {
             rendered_part.truncate(rendered_part.len() - suffix.len());
         }
 
+        if skip_empty && rendered_part.is_empty() {
+            return Ok(None);
+        }
         rendered.push(rendered_part);
     }
-    Ok(rendered)
+    Ok(Some(rendered))
 }
 
-fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
+pub(crate) fn build_glob_set(patterns: &[String]) -> Result<GlobSet> {
     let mut builder = GlobSetBuilder::new();
     for pattern in patterns {
         let glob = Glob::new(pattern).map_err(|e| DicecutError::GlobPattern {
@@ -99,22 +99,7 @@ pub fn get_or_clone(url: &str, git_ref: Option<&str>) -> Result<(PathBuf, Option
         return Ok((cached_path, metadata.commit_sha));
     }
 
-    std::fs::create_dir_all(&cache_dir).map_err(|e| DicecutError::Io {
-        context: format!("creating cache directory {}", cache_dir.display()),
-        source: e,
-    })?;
-
-    // Acquire an exclusive advisory lock for this cache key.
-    // Blocks until the lock is available; automatically released on drop/exit.
-    let lock_path = cache_dir.join(format!("{key}.lock"));
-    let lock_file = std::fs::File::create(&lock_path).map_err(|e| DicecutError::Io {
-        context: format!("creating lock file {}", lock_path.display()),
-        source: e,
-    })?;
-    lock_file.lock_exclusive().map_err(|e| DicecutError::Io {
-        context: format!("acquiring cache lock for {key}"),
-        source: e,
-    })?;
+    let _lock = lock_cache_key(&cache_dir, &key)?;
 
     // Re-check cache after acquiring lock — another process may have populated it.
     if cached_path.exists() && cached_path.join(CACHE_METADATA_FILE).exists() {
@@ -137,6 +122,28 @@ pub fn get_or_clone(url: &str, git_ref: Option<&str>) -> Result<(PathBuf, Option
     Ok((cached_path, commit_sha))
 }
 
+/// Acquire an exclusive advisory lock for a cache key.
+///
+/// Blocks until the lock is available; released when the returned file is
+/// dropped or the process exits.
+fn lock_cache_key(cache_dir: &Path, key: &str) -> Result<std::fs::File> {
+    std::fs::create_dir_all(cache_dir).map_err(|e| DicecutError::Io {
+        context: format!("creating cache directory {}", cache_dir.display()),
+        source: e,
+    })?;
+
+    let lock_path = cache_dir.join(format!("{key}.lock"));
+    let lock_file = std::fs::File::create(&lock_path).map_err(|e| DicecutError::Io {
+        context: format!("creating lock file {}", lock_path.display()),
+        source: e,
+    })?;
+    lock_file.lock_exclusive().map_err(|e| DicecutError::Io {
+        context: format!("acquiring cache lock for {key}"),
+        source: e,
+    })?;
+    Ok(lock_file)
+}
+
 /// Serialize and write cache metadata into the cloned template directory.
 fn write_cache_metadata(
     dir: &Path,
@@ -280,6 +287,94 @@ pub fn clear_cache(url: Option<&str>) -> Result<()> {
     Ok(())
 }
 
+/// Remove cached templates that were cloned more than `older_than` ago.
+///
+/// Entries with an unreadable `cached_at` timestamp are left alone.
+/// Returns the removed entries.
+pub fn prune_cache(older_than: std::time::Duration) -> Result<Vec<CachedTemplate>> {
+    let cache_dir = get_cache_dir()?;
+    let now: u64 = unix_timestamp_secs().parse().unwrap_or_default();
+    let cutoff = now.saturating_sub(older_than.as_secs());
+    let is_stale = |metadata: &CacheMetadata| {
+        metadata
+            .cached_at
+            .parse::<u64>()
+            .is_ok_and(|cached_at| cached_at < cutoff)
+    };
+
+    let mut removed = Vec::new();
+    for entry in list_cached()? {
+        if !is_stale(&entry.metadata) {
+            continue;
+        }
+
+        let _lock = lock_cache_key(&cache_dir, &entry.key)?;
+
+        // Re-check after acquiring the lock — another process may have
+        // refreshed or removed the entry in the meantime.
+        match read_cache_metadata(&entry.path) {
+            Ok(metadata) if is_stale(&metadata) => {}
+            _ => continue,
+        }
+
+        std::fs::remove_dir_all(&entry.path).map_err(|e| DicecutError::Io {
+            context: format!("removing cached template {}", entry.path.display()),
+            source: e,
+        })?;
+        removed.push(entry);
+    }
+
+    Ok(removed)
+}
+
+/// Re-clone cached templates in place.
+///
+/// If `url` is provided, only entries matching that URL are refreshed;
+/// otherwise every cached template is. Each entry keeps its ref.
+/// Returns the refreshed entries with their new metadata.
+pub fn refresh_cache(url: Option<&str>) -> Result<Vec<CachedTemplate>> {
+    let cache_dir = get_cache_dir()?;
+    let normalized_input = url.map(normalize_url);
+
+    let mut refreshed = Vec::new();
+    for entry in list_cached()? {
+        if let Some(input) = normalized_input {
+            if normalize_url(&entry.metadata.url) != input {
+                continue;
+            }
+        }
+
+        let _lock = lock_cache_key(&cache_dir, &entry.key)?;
+        let git_ref = entry.metadata.git_ref.as_deref();
+        let clone_result = clone_template(&entry.metadata.url, git_ref)?;
+        write_cache_metadata(
+            clone_result.dir.path(),
+            &entry.metadata.url,
+            git_ref,
+            clone_result.commit_sha.as_deref(),
+        )?;
+        place_in_cache(clone_result, &entry.path)?;
+
+        refreshed.push(CachedTemplate {
+            metadata: read_cache_metadata(&entry.path)?,
+            ..entry
+        });
+    }
+
+    Ok(refreshed)
+}
+
+/// Total size in bytes of the files in a cached template directory.
+pub fn cache_entry_size(path: &Path) -> u64 {
+    walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter_map(|e| e.metadata().ok())
+        .filter(|m| m.is_file())
+        .map(|m| m.len())
+        .sum()
+}
+
 /// Recursively copy a directory, skipping symlinks.
 fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
     std::fs::create_dir_all(dst).map_err(|e| DicecutError::Io {
@@ -332,6 +427,7 @@ fn unix_timestamp_secs() -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::git;
     use rstest::rstest;
     use std::sync::Mutex;
 
@@ -803,6 +899,83 @@ mod tests {
         assert_eq!(path, cache_dir.join(&key));
     }
 
+    // ── prune / refresh / size ──────────────────────────────────────
+
+    #[test]
+    fn prune_cache_removes_only_old_entries() {
+        let (_lock, tmp) = setup_cache_env();
+        let cache_dir = tmp.path().to_path_buf();
+
+        // Fake entries are stamped 1700000000 (Nov 2023).
+        create_fake_cache_entry(&cache_dir, "old-entry", "https://github.com/u/old", None);
+        let fresh = cache_dir.join("fresh-entry");
+        std::fs::create_dir_all(&fresh).unwrap();
+        write_cache_metadata(&fresh, "https://github.com/u/fresh", None, None).unwrap();
+
+        let removed = prune_cache(std::time::Duration::from_secs(30 * 86400)).unwrap();
+
+        assert_eq!(removed.len(), 1);
+        assert_eq!(removed[0].key, "old-entry");
+        assert!(!cache_dir.join("old-entry").exists());
+        assert!(cache_dir.join("old-entry.lock").exists());
+        assert!(fresh.exists());
+    }
+
+    #[test]
+    fn prune_cache_skips_unparseable_timestamps() {
+        let (_lock, tmp) = setup_cache_env();
+        let entry_dir = tmp.path().join("odd-entry");
+        std::fs::create_dir_all(&entry_dir).unwrap();
+        std::fs::write(
+            entry_dir.join(CACHE_METADATA_FILE),
+            "url = \"https://github.com/u/odd\"\ncached_at = \"yesterday\"\n",
+        )
+        .unwrap();
+
+        let removed = prune_cache(std::time::Duration::ZERO).unwrap();
+
+        assert!(removed.is_empty());
+        assert!(entry_dir.exists());
+    }
+
+    #[test]
+    fn refresh_cache_reclones_matching_entries() {
+        let (_lock, tmp) = setup_cache_env();
+        let cache_dir = tmp.path().to_path_buf();
+
+        let repo = tempfile::tempdir().unwrap();
+        git(repo.path(), &["init", "--quiet"]);
+        std::fs::write(
+            repo.path().join("diecut.toml"),
+            "[template]\nname = \"v2\"\n",
+        )
+        .unwrap();
+        git(repo.path(), &["add", "-A"]);
+        git(repo.path(), &["commit", "--quiet", "-m", "v2"]);
+
+        let url = repo.path().display().to_string();
+        let key = cache_key(&url, None);
+        let entry = create_fake_cache_entry_with_sha(&cache_dir, &key, &url, None, Some("stale"));
+        create_fake_cache_entry(&cache_dir, "other", "https://github.com/u/other", None);
+
+        let refreshed = refresh_cache(Some(&url)).unwrap();
+
+        assert_eq!(refreshed.len(), 1);
+        assert_ne!(refreshed[0].metadata.commit_sha.as_deref(), Some("stale"));
+        assert_ne!(refreshed[0].metadata.cached_at, "1700000000");
+        assert!(entry.join("diecut.toml").exists());
+    }
+
+    #[test]
+    fn cache_entry_size_sums_file_sizes() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("sub")).unwrap();
+        std::fs::write(dir.path().join("a.txt"), "12345").unwrap();
+        std::fs::write(dir.path().join("sub/b.txt"), "123").unwrap();
+
+        assert_eq!(cache_entry_size(dir.path()), 8);
+    }
+
     #[test]
     fn cache_metadata_deserializes_without_commit_sha() {
         // Old cache entries won't have commit_sha — verify backwards compat
@@ -1,3 +1,4 @@
+use std::path::Path;
 use std::process::Command;
 
 use crate::error::{DicecutError, Result};
@@ -28,13 +29,8 @@ fn classify_clone_error(stderr: &str) -> String {
     }
 }
 
-/// Clone a git repository to a temporary directory, optionally checking out a
-/// specific ref. Rejects `file://` URLs and warns on `http://`.
-///
-/// Uses the system `git` binary so that the user's full credential stack
-/// (macOS Keychain, SSH agent, `gh auth`, credential helpers, etc.) is
-/// inherited automatically.
-pub fn clone_template(url: &str, git_ref: Option<&str>) -> Result<CloneResult> {
+/// Reject `file://` URLs, warn on `http://`, and make sure `git` is available.
+fn check_clone_preconditions(url: &str) -> Result<()> {
     if url.starts_with("file://") {
         return Err(DicecutError::UnsafeUrl {
             url: url.to_string(),
@@ -51,27 +47,26 @@ pub fn clone_template(url: &str, git_ref: Option<&str>) -> Result<CloneResult> {
         .output()
         .map_err(|_| DicecutError::GitNotFound)?;
 
-    let tmp_dir = tempfile::tempdir().map_err(|e| DicecutError::Io {
+    Ok(())
+}
+
+fn clone_tempdir() -> Result<tempfile::TempDir> {
+    tempfile::tempdir().map_err(|e| DicecutError::Io {
         context: "creating temporary directory for git clone".into(),
         source: e,
-    })?;
-
-    let mut cmd = Command::new("git");
-    cmd.env("GIT_TERMINAL_PROMPT", "0")
-        .arg("clone")
-        .arg("--depth")
-        .arg("1");
-
-    if let Some(ref_name) = git_ref {
-        cmd.arg("--branch").arg(ref_name);
-    }
-
-    cmd.arg(url).arg(tmp_dir.path());
+    })
+}
 
-    let output = cmd.output().map_err(|e| DicecutError::Io {
-        context: "running git clone".into(),
-        source: e,
-    })?;
+/// Run a git command with terminal prompts disabled, mapping a non-zero exit
+/// to a [`DicecutError::GitClone`] for `url`.
+fn run_git(cmd: &mut Command, url: &str, context: &str) -> Result<()> {
+    let output = cmd
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .map_err(|e| DicecutError::Io {
+            context: format!("running {context}"),
+            source: e,
+        })?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -81,9 +76,13 @@ pub fn clone_template(url: &str, git_ref: Option<&str>) -> Result<CloneResult> {
         });
     }
 
+    Ok(())
+}
+
+fn rev_parse_head(dir: &Path) -> Result<Option<String>> {
     let rev_output = Command::new("git")
         .arg("-C")
-        .arg(tmp_dir.path())
+        .arg(dir)
         .arg("rev-parse")
         .arg("HEAD")
         .output()
@@ -92,15 +91,137 @@ pub fn clone_template(url: &str, git_ref: Option<&str>) -> Result<CloneResult> {
             source: e,
         })?;
 
-    let commit_sha = if rev_output.status.success() {
-        Some(
+    if rev_output.status.success() {
+        Ok(Some(
             String::from_utf8_lossy(&rev_output.stdout)
                 .trim()
                 .to_string(),
-        )
+        ))
     } else {
-        None
-    };
+        Ok(None)
+    }
+}
+
+/// Whether a ref looks like a full or abbreviated commit SHA.
+pub fn looks_like_commit_sha(git_ref: &str) -> bool {
+    (7..=40).contains(&git_ref.len()) && git_ref.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Clone a git repository to a temporary directory, optionally checking out a
+/// specific ref. Rejects `file://` URLs and warns on `http://`.
+///
+/// The ref may be a branch, a tag, or a full or abbreviated commit SHA.
+/// `git clone --branch` only accepts branches and tags, so full SHAs are
+/// fetched directly, and abbreviated ones are retried as commits when
+/// `--branch` rejects them.
+///
+/// Uses the system `git` binary so that the user's full credential stack
+/// (macOS Keychain, SSH agent, `gh auth`, credential helpers, etc.) is
+/// inherited automatically.
+pub fn clone_template(url: &str, git_ref: Option<&str>) -> Result<CloneResult> {
+    match git_ref {
+        Some(sha) if sha.len() == 40 && looks_like_commit_sha(sha) => {
+            clone_template_at_commit(url, sha)
+        }
+        Some(r) if looks_like_commit_sha(r) => {
+            clone_template_at_ref(url, git_ref).or_else(|_| clone_template_at_commit(url, r))
+        }
+        _ => clone_template_at_ref(url, git_ref),
+    }
+}
+
+/// Shallow-clone a branch or tag (or the default branch when `git_ref` is `None`).
+fn clone_template_at_ref(url: &str, git_ref: Option<&str>) -> Result<CloneResult> {
+    check_clone_preconditions(url)?;
+
+    let tmp_dir = clone_tempdir()?;
+
+    let mut cmd = Command::new("git");
+    cmd.arg("clone").arg("--depth").arg("1");
+
+    if let Some(ref_name) = git_ref {
+        cmd.arg("--branch").arg(ref_name);
+    }
+
+    cmd.arg(url).arg(tmp_dir.path());
+
+    run_git(&mut cmd, url, "git clone")?;
+
+    let commit_sha = rev_parse_head(tmp_dir.path())?;
+
+    Ok(CloneResult {
+        dir: tmp_dir,
+        commit_sha,
+    })
+}
+
+/// Fetch a single commit of a git repository into a temporary directory.
+///
+/// Tries a shallow fetch of the commit first. Servers that refuse to serve
+/// unadvertised objects (and abbreviated SHAs, which cannot be fetched
+/// directly) fall back to fetching the full history before checking out.
+pub fn clone_template_at_commit(url: &str, commit: &str) -> Result<CloneResult> {
+    check_clone_preconditions(url)?;
+
+    let tmp_dir = clone_tempdir()?;
+    let dir = tmp_dir.path();
+
+    run_git(
+        Command::new("git").arg("init").arg("--quiet").arg(dir),
+        url,
+        "git init",
+    )?;
+    run_git(
+        Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["remote", "add", "origin"])
+            .arg(url),
+        url,
+        "git remote add",
+    )?;
+
+    let shallow = run_git(
+        Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["fetch", "--quiet", "--depth", "1", "origin", commit]),
+        url,
+        "git fetch",
+    )
+    .and_then(|_| {
+        run_git(
+            Command::new("git").arg("-C").arg(dir).args([
+                "checkout",
+                "--quiet",
+                "--detach",
+                "FETCH_HEAD",
+            ]),
+            url,
+            "git checkout",
+        )
+    });
+
+    if shallow.is_err() {
+        run_git(
+            Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .args(["fetch", "--quiet", "--tags", "origin"]),
+            url,
+            "git fetch",
+        )?;
+        run_git(
+            Command::new("git")
+                .arg("-C")
+                .arg(dir)
+                .args(["checkout", "--quiet", "--detach", commit]),
+            url,
+            "git checkout",
+        )?;
+    }
+
+    let commit_sha = rev_parse_head(dir)?;
 
     Ok(CloneResult {
         dir: tmp_dir,
@@ -111,6 +232,7 @@ pub fn clone_template(url: &str, git_ref: Option<&str>) -> Result<CloneResult> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_support::git;
     use rstest::rstest;
 
     #[test]
@@ -143,6 +265,56 @@ mod tests {
         }
     }
 
+    fn init_repo_with_commits(messages: &[&str]) -> (tempfile::TempDir, Vec<String>) {
+        let repo = tempfile::tempdir().unwrap();
+        git(repo.path(), &["init", "--quiet"]);
+        let mut shas = Vec::new();
+        for message in messages {
+            std::fs::write(repo.path().join("version.txt"), message).unwrap();
+            git(repo.path(), &["add", "-A"]);
+            git(repo.path(), &["commit", "--quiet", "-m", message]);
+            shas.push(git(repo.path(), &["rev-parse", "HEAD"]));
+        }
+        git(repo.path(), &["tag", "latest"]);
+        (repo, shas)
+    }
+
+    #[rstest]
+    #[case::full_sha(40)]
+    #[case::abbreviated_sha(8)]
+    fn clone_checks_out_commit_sha(#[case] len: usize) {
+        let (repo, shas) = init_repo_with_commits(&["v1", "v2"]);
+        let url = repo.path().display().to_string();
+
+        let result = clone_template(&url, Some(&shas[0][..len])).unwrap();
+
+        assert_eq!(result.commit_sha.as_deref(), Some(shas[0].as_str()));
+        assert_eq!(
+            std::fs::read_to_string(result.dir.path().join("version.txt")).unwrap(),
+            "v1"
+        );
+    }
+
+    #[test]
+    fn clone_checks_out_tag() {
+        let (repo, shas) = init_repo_with_commits(&["v1", "v2"]);
+        let url = repo.path().display().to_string();
+
+        let result = clone_template(&url, Some("latest")).unwrap();
+
+        assert_eq!(result.commit_sha.as_deref(), Some(shas[1].as_str()));
+    }
+
+    #[rstest]
+    #[case("0123abc", true)]
+    #[case("0123456789abcdef0123456789abcdef01234567", true)]
+    #[case("abc12", false)]
+    #[case("v2.1.0", false)]
+    #[case("main", false)]
+    fn looks_like_commit_sha_cases(#[case] input: &str, #[case] expected: bool) {
+        assert_eq!(looks_like_commit_sha(input), expected);
+    }
+
     #[test]
     fn clone_result_has_expected_fields() {
         let tmp = tempfile::tempdir().unwrap();
@@ -14,27 +14,38 @@ pub enum GitProtocol {
 impl std::str::FromStr for GitProtocol {
     type Err = DicecutError;
     fn from_str(s: &str) -> Result<Self> {
-        match s {
-            "ssh" => Ok(GitProtocol::Ssh),
-            "https" => Ok(GitProtocol::Https),
-            other => Err(DicecutError::InvalidProtocol {
-                value: other.to_string(),
-                config_key: "DIECUT_GIT_PROTOCOL",
-            }),
-        }
+        parse_protocol(s, "DIECUT_GIT_PROTOCOL")
+    }
+}
+
+fn parse_protocol(value: &str, config_key: &'static str) -> Result<GitProtocol> {
+    match value {
+        "ssh" => Ok(GitProtocol::Ssh),
+        "https" => Ok(GitProtocol::Https),
+        other => Err(DicecutError::InvalidProtocol {
+            value: other.to_string(),
+            config_key,
+        }),
     }
 }
 
 /// Resolve the git protocol to use for shortcode expansion.
 ///
-/// Precedence: CLI flag > `DIECUT_GIT_PROTOCOL` env var > built-in default (SSH).
-pub fn resolve_git_protocol(cli_flag: Option<GitProtocol>) -> Result<GitProtocol> {
+/// Precedence: CLI flag > `DIECUT_GIT_PROTOCOL` env var > `protocol` in the
+/// user config > built-in default (SSH).
+pub fn resolve_git_protocol(
+    cli_flag: Option<GitProtocol>,
+    user_config: Option<&str>,
+) -> Result<GitProtocol> {
     if let Some(p) = cli_flag {
         return Ok(p);
     }
-    match std::env::var("DIECUT_GIT_PROTOCOL") {
-        Ok(value) => value.parse(),
-        Err(_) => Ok(GitProtocol::default()),
+    if let Ok(value) = std::env::var("DIECUT_GIT_PROTOCOL") {
+        return value.parse();
+    }
+    match user_config {
+        Some(value) => parse_protocol(value, "the user config (protocol)"),
+        None => Ok(GitProtocol::default()),
     }
 }
 
@@ -163,12 +174,28 @@ fn expand_user_abbreviation(
     }))
 }
 
+/// Split a trailing `@ref` off a remote template argument (`gh:org/tpl@v2.1.0`).
+///
+/// The `@` in scp-style URLs (`git@github.com:...`) and URL credentials
+/// (`https://user@host/...`) is not a ref separator: a ref must follow the
+/// repository path and cannot contain `:`.
+fn split_inline_ref(input: &str) -> (&str, Option<&str>) {
+    let Some((base, git_ref)) = input.rsplit_once('@') else {
+        return (input, None);
+    };
+    let path = base.split_once("://").map_or(base, |(_, rest)| rest);
+    if git_ref.is_empty() || git_ref.contains(':') || !path.contains('/') {
+        return (input, None);
+    }
+    (base, Some(git_ref))
+}
+
 /// Check if an input string starts with any built-in shortcode prefix.
 fn is_abbreviation(input: &str) -> bool {
     SHORTCODES.iter().any(|s| input.starts_with(s.prefix))
 }
 
-fn is_git_url(input: &str) -> bool {
+pub(crate) fn is_git_url(input: &str) -> bool {
     input.starts_with("https://")
         || input.starts_with("http://")
         || input.starts_with("git@")
@@ -201,31 +228,36 @@ pub fn format_resolved_source(source: &TemplateSource) -> String {
 /// Resolve a template argument to a [`TemplateSource`].
 ///
 /// Handles user abbreviations, built-in shortcodes, explicit git URLs, and local paths.
+/// Remote sources may carry an inline ref (`gh:user/repo@v1.2.0`); an explicit
+/// [`ResolveOptions::git_ref`] takes precedence over it.
 pub fn resolve_source(template_arg: &str, opts: ResolveOptions<'_>) -> Result<TemplateSource> {
+    let (remote_arg, inline_ref) = split_inline_ref(template_arg);
+    let git_ref = opts.git_ref.or(inline_ref).map(String::from);
+
     if let Some(abbrevs) = opts.user_abbreviations {
-        if let Some(result) = expand_user_abbreviation(template_arg, abbrevs) {
+        if let Some(result) = expand_user_abbreviation(remote_arg, abbrevs) {
             let expanded = result?;
             return Ok(TemplateSource::Git {
                 url: expanded.url,
-                git_ref: opts.git_ref.map(String::from),
+                git_ref,
                 subpath: expanded.subpath,
             });
         }
     }
 
-    if is_abbreviation(template_arg) {
-        let expanded = expand_abbreviation(template_arg, opts.protocol)?;
+    if is_abbreviation(remote_arg) {
+        let expanded = expand_abbreviation(remote_arg, opts.protocol)?;
         return Ok(TemplateSource::Git {
             url: expanded.url,
-            git_ref: opts.git_ref.map(String::from),
+            git_ref,
             subpath: expanded.subpath,
         });
     }
 
-    if is_git_url(template_arg) {
+    if is_git_url(remote_arg) {
         return Ok(TemplateSource::Git {
-            url: template_arg.to_string(),
-            git_ref: opts.git_ref.map(String::from),
+            url: remote_arg.to_string(),
+            git_ref,
             subpath: None,
         });
     }
@@ -353,6 +385,65 @@ mod tests {
         assert!(result.is_err());
     }
 
+    // ── Inline refs ────────────────────────────────────────────────────
+
+    #[rstest]
+    #[case("gh:org/tpl@v2.1.0", "gh:org/tpl", Some("v2.1.0"))]
+    #[case("gh:org/tpl/sub@feature/x", "gh:org/tpl/sub", Some("feature/x"))]
+    #[case("git@github.com:org/tpl.git", "git@github.com:org/tpl.git", None)]
+    #[case(
+        "git@github.com:org/tpl.git@abc1234",
+        "git@github.com:org/tpl.git",
+        Some("abc1234")
+    )]
+    #[case("https://user@host.com/tpl.git", "https://user@host.com/tpl.git", None)]
+    #[case(
+        "https://host.com/tpl.git@main",
+        "https://host.com/tpl.git",
+        Some("main")
+    )]
+    #[case("gh:org/tpl@", "gh:org/tpl@", None)]
+    fn split_inline_ref_cases(
+        #[case] input: &str,
+        #[case] exp_base: &str,
+        #[case] exp_ref: Option<&str>,
+    ) {
+        assert_eq!(split_inline_ref(input), (exp_base, exp_ref));
+    }
+
+    #[test]
+    fn resolve_source_inline_ref() {
+        let source = resolve_source("gh:user/repo/sub@v2.1.0", ResolveOptions::default()).unwrap();
+        match source {
+            TemplateSource::Git {
+                url,
+                git_ref,
+                subpath,
+            } => {
+                assert_eq!(url, "git@github.com:user/repo.git");
+                assert_eq!(git_ref.as_deref(), Some("v2.1.0"));
+                assert_eq!(subpath.as_deref(), Some("sub"));
+            }
+            _ => panic!("expected Git source"),
+        }
+    }
+
+    #[test]
+    fn resolve_source_explicit_ref_overrides_inline() {
+        let opts = ResolveOptions {
+            git_ref: Some("main"),
+            ..Default::default()
+        };
+        let source = resolve_source("https://example.com/repo.git@v1", opts).unwrap();
+        match source {
+            TemplateSource::Git { url, git_ref, .. } => {
+                assert_eq!(url, "https://example.com/repo.git");
+                assert_eq!(git_ref.as_deref(), Some("main"));
+            }
+            _ => panic!("expected Git source"),
+        }
+    }
+
     // ── Subpath parsing ────────────────────────────────────────────────
 
     #[rstest]
@@ -522,7 +613,7 @@ mod protocol_resolution_tests {
     #[serial]
     fn no_flag_no_env_defaults_to_ssh() {
         clear_env();
-        let result = resolve_git_protocol(None).unwrap();
+        let result = resolve_git_protocol(None, None).unwrap();
         assert_eq!(result, GitProtocol::Ssh);
     }
 
@@ -530,7 +621,7 @@ mod protocol_resolution_tests {
     #[serial]
     fn flag_ssh_returns_ssh() {
         clear_env();
-        let result = resolve_git_protocol(Some(GitProtocol::Ssh)).unwrap();
+        let result = resolve_git_protocol(Some(GitProtocol::Ssh), None).unwrap();
         assert_eq!(result, GitProtocol::Ssh);
     }
 
@@ -538,7 +629,7 @@ mod protocol_resolution_tests {
     #[serial]
     fn flag_https_returns_https() {
         clear_env();
-        let result = resolve_git_protocol(Some(GitProtocol::Https)).unwrap();
+        let result = resolve_git_protocol(Some(GitProtocol::Https), None).unwrap();
         assert_eq!(result, GitProtocol::Https);
     }
 
@@ -547,7 +638,7 @@ mod protocol_resolution_tests {
     fn env_https_returns_https_when_no_flag() {
         clear_env();
         std::env::set_var("DIECUT_GIT_PROTOCOL", "https");
-        let result = resolve_git_protocol(None).unwrap();
+        let result = resolve_git_protocol(None, None).unwrap();
         clear_env();
         assert_eq!(result, GitProtocol::Https);
     }
@@ -557,7 +648,7 @@ mod protocol_resolution_tests {
     fn env_ssh_returns_ssh_when_no_flag() {
         clear_env();
         std::env::set_var("DIECUT_GIT_PROTOCOL", "ssh");
-        let result = resolve_git_protocol(None).unwrap();
+        let result = resolve_git_protocol(None, None).unwrap();
         clear_env();
         assert_eq!(result, GitProtocol::Ssh);
     }
@@ -567,7 +658,7 @@ mod protocol_resolution_tests {
     fn flag_overrides_env() {
         clear_env();
         std::env::set_var("DIECUT_GIT_PROTOCOL", "ssh");
-        let result = resolve_git_protocol(Some(GitProtocol::Https)).unwrap();
+        let result = resolve_git_protocol(Some(GitProtocol::Https), None).unwrap();
         clear_env();
         assert_eq!(result, GitProtocol::Https);
     }
@@ -577,7 +668,7 @@ mod protocol_resolution_tests {
     fn invalid_env_value_errors() {
         clear_env();
         std::env::set_var("DIECUT_GIT_PROTOCOL", "tcp");
-        let result = resolve_git_protocol(None);
+        let result = resolve_git_protocol(None, None);
         clear_env();
         assert!(result.is_err());
         assert!(matches!(
@@ -586,12 +677,41 @@ mod protocol_resolution_tests {
         ));
     }
 
+    #[test]
+    #[serial]
+    fn user_config_used_when_no_flag_or_env() {
+        clear_env();
+        let result = resolve_git_protocol(None, Some("https")).unwrap();
+        assert_eq!(result, GitProtocol::Https);
+    }
+
+    #[test]
+    #[serial]
+    fn env_overrides_user_config() {
+        clear_env();
+        std::env::set_var("DIECUT_GIT_PROTOCOL", "ssh");
+        let result = resolve_git_protocol(None, Some("https")).unwrap();
+        clear_env();
+        assert_eq!(result, GitProtocol::Ssh);
+    }
+
+    #[test]
+    #[serial]
+    fn invalid_user_config_value_errors() {
+        clear_env();
+        let result = resolve_git_protocol(None, Some("tcp"));
+        assert!(matches!(
+            result,
+            Err(DicecutError::InvalidProtocol { config_key, .. }) if config_key.contains("user config")
+        ));
+    }
+
     #[test]
     #[serial]
     fn empty_env_value_errors() {
         clear_env();
         std::env::set_var("DIECUT_GIT_PROTOCOL", "");
-        let result = resolve_git_protocol(None);
+        let result = resolve_git_protocol(None, None);
         clear_env();
         assert!(result.is_err());
     }
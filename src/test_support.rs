@@ -0,0 +1,41 @@
+//! Fixtures shared by the unit tests.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Write each `(relative path, content)` pair under `dir`, creating parent directories.
+pub(crate) fn write_files(dir: &Path, files: &[(&str, &str)]) {
+    for (path, content) in files {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+}
+
+/// A temporary directory holding `files`.
+pub(crate) fn temp_dir_with(files: &[(&str, &str)]) -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    write_files(dir.path(), files);
+    dir
+}
+
+/// A native template: `config` as its `diecut.toml` and `files` under `template/`.
+pub(crate) fn native_template(config: &str, files: &[(&str, &str)]) -> tempfile::TempDir {
+    let dir = temp_dir_with(&[("diecut.toml", config)]);
+    write_files(&dir.path().join("template"), files);
+    dir
+}
+
+/// Run git in `dir` with a fixed identity and return its trimmed stdout.
+pub(crate) fn git(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .output()
+        .unwrap();
+    assert!(output.status.success(), "git {args:?} failed");
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
@@ -0,0 +1,98 @@
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::{DicecutError, Result};
+
+/// Result of a three-way merge of a single file.
+pub enum MergeOutcome {
+    /// Both sides merged without overlapping edits.
+    Clean(Vec<u8>),
+    /// The merged content contains conflict markers.
+    Conflicted(Vec<u8>),
+}
+
+/// Three-way merge text content with `git merge-file`.
+///
+/// `ours` is the project's current content, `base` the file as rendered from
+/// the previously applied template commit, and `theirs` the file as rendered
+/// from the new template commit.
+pub fn merge_text(file: &Path, base: &[u8], ours: &[u8], theirs: &[u8]) -> Result<MergeOutcome> {
+    let tmp = tempfile::tempdir().map_err(|e| DicecutError::Io {
+        context: "creating temporary directory for merge".into(),
+        source: e,
+    })?;
+
+    let write = |name: &str, content: &[u8]| -> Result<std::path::PathBuf> {
+        let path = tmp.path().join(name);
+        std::fs::write(&path, content).map_err(|e| DicecutError::Io {
+            context: format!("writing {}", path.display()),
+            source: e,
+        })?;
+        Ok(path)
+    };
+    let ours_path = write("ours", ours)?;
+    let base_path = write("base", base)?;
+    let theirs_path = write("theirs", theirs)?;
+
+    let output = Command::new("git")
+        .arg("merge-file")
+        .arg("-p")
+        .args([
+            "-L",
+            "project",
+            "-L",
+            "previous template",
+            "-L",
+            "new template",
+        ])
+        .arg(&ours_path)
+        .arg(&base_path)
+        .arg(&theirs_path)
+        .output()
+        .map_err(|_| DicecutError::GitNotFound)?;
+
+    // git merge-file exits with the number of conflicts, or a negative value on error.
+    match output.status.code() {
+        Some(0) => Ok(MergeOutcome::Clean(output.stdout)),
+        Some(n) if (1..128).contains(&n) => Ok(MergeOutcome::Conflicted(output.stdout)),
+        _ => Err(DicecutError::MergeFailed {
+            file: file.display().to_string(),
+            reason: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_text_combines_independent_edits() {
+        let base = b"one\ntwo\nthree\nfour\nfive\n";
+        let ours = b"one\ntwo (local)\nthree\nfour\nfive\n";
+        let theirs = b"one\ntwo\nthree\nfour\nfive (template)\n";
+
+        match merge_text(Path::new("f.txt"), base, ours, theirs).unwrap() {
+            MergeOutcome::Clean(content) => {
+                assert_eq!(content, b"one\ntwo (local)\nthree\nfour\nfive (template)\n");
+            }
+            MergeOutcome::Conflicted(_) => panic!("expected a clean merge"),
+        }
+    }
+
+    #[test]
+    fn merge_text_marks_overlapping_edits() {
+        let base = b"name = \"demo\"\n";
+        let ours = b"name = \"local\"\n";
+        let theirs = b"name = \"template\"\n";
+
+        match merge_text(Path::new("f.txt"), base, ours, theirs).unwrap() {
+            MergeOutcome::Conflicted(content) => {
+                let text = String::from_utf8(content).unwrap();
+                assert!(text.contains("<<<<<<< project"));
+                assert!(text.contains(">>>>>>> new template"));
+            }
+            MergeOutcome::Clean(_) => panic!("expected a conflict"),
+        }
+    }
+}
@@ -0,0 +1,501 @@
+pub mod merge;
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use crate::adapter::{resolve_template_as, ResolvedTemplate};
+use crate::answers::{load_answers, write_answers, TemplateOrigin};
+use crate::error::{DicecutError, Result};
+use crate::prompt::{collect_variables, PromptOptions};
+use crate::render::plan_render;
+use crate::template::clone::{clone_template, clone_template_at_commit};
+
+use merge::{merge_text, MergeOutcome};
+
+pub struct UpdateOptions {
+    /// Directory of the previously generated project.
+    pub project_dir: PathBuf,
+    /// Branch, tag, or commit SHA to update to, instead of the recorded ref.
+    pub git_ref: Option<String>,
+    /// Use default values for newly added variables without prompting.
+    pub defaults: bool,
+    /// Never prompt; fail listing every new variable that has no value.
+    pub no_input: bool,
+}
+
+/// What happened to the project's files during an update.
+#[derive(Debug, Default)]
+pub struct UpdateReport {
+    /// Template commit the project was previously generated from.
+    pub old_commit: String,
+    /// Template commit the project was updated to.
+    pub new_commit: Option<String>,
+    /// Files added or changed without conflicts.
+    pub updated: Vec<PathBuf>,
+    /// Files removed because the new template no longer ships them.
+    pub removed: Vec<PathBuf>,
+    /// Files the new template dropped, left in place because they were modified locally.
+    pub kept: Vec<PathBuf>,
+    /// Files written with conflict markers.
+    pub conflicts: Vec<PathBuf>,
+    /// Files whose new template version was written to a `.rej` file next to them.
+    pub rejected: Vec<PathBuf>,
+}
+
+impl UpdateReport {
+    pub fn is_up_to_date(&self) -> bool {
+        self.new_commit.as_deref() == Some(self.old_commit.as_str())
+    }
+}
+
+/// Re-apply the latest version of a project's template.
+///
+/// Renders the template at the commit recorded in the answers file and at the
+/// current commit of the recorded (or requested) ref, both with the saved answers, and
+/// three-way merges the difference into the project. Overlapping text edits
+/// get conflict markers; binary or otherwise unmergeable files get a `.rej` file.
+pub fn update_project(options: UpdateOptions) -> Result<UpdateReport> {
+    let project_dir = &options.project_dir;
+    let saved = load_answers(project_dir)?;
+
+    if saved.template_source.is_empty() {
+        return Err(DicecutError::UpdateUnsupported {
+            path: project_dir.clone(),
+            reason: "the answers file does not record a git template source".into(),
+        });
+    }
+    let old_commit = saved
+        .commit_sha
+        .clone()
+        .ok_or_else(|| DicecutError::UpdateUnsupported {
+            path: project_dir.clone(),
+            reason: "the answers file does not record the template commit".into(),
+        })?;
+
+    let url = saved.template_source.as_str();
+    let subpath = saved.template_subpath.as_deref();
+
+    let git_ref = options.git_ref.clone().or(saved.template_ref.clone());
+    let new_clone = clone_template(url, git_ref.as_deref())?;
+    let mut report = UpdateReport {
+        old_commit: old_commit.clone(),
+        new_commit: new_clone.commit_sha.clone(),
+        ..Default::default()
+    };
+    if report.is_up_to_date() {
+        return Ok(report);
+    }
+    let format = saved.format.as_deref();
+    let new_resolved = resolve_at(project_dir, new_clone.dir.path(), subpath, format)?;
+
+    let old_clone = clone_template_at_commit(url, &old_commit)?;
+    let old_resolved = resolve_at(project_dir, old_clone.dir.path(), subpath, format)?;
+
+    crate::print_warnings(&new_resolved.warnings);
+
+    let saved_values = saved.values();
+    let new_variables = collect_variables(
+        &new_resolved.config,
+        &PromptOptions {
+            answers: saved_values.clone(),
+            use_defaults: options.defaults,
+            no_input: options.no_input,
+            ..Default::default()
+        },
+    )?;
+
+    // Answers given for the new version also cover variables the old version
+    // needs but that were never saved (e.g. secrets), so nobody is asked twice.
+    let mut old_answers = saved_values;
+    for (name, value) in &new_variables {
+        old_answers
+            .entry(name.clone())
+            .or_insert_with(|| value.clone());
+    }
+    let old_variables = collect_variables(
+        &old_resolved.config,
+        &PromptOptions {
+            answers: old_answers,
+            use_defaults: true,
+            no_input: options.no_input,
+            ..Default::default()
+        },
+    )?;
+
+    let base = render_files(&old_resolved, &old_variables)?;
+    let theirs = render_files(&new_resolved, &new_variables)?;
+
+    let answers_file = PathBuf::from(&new_resolved.config.answers.file);
+    let paths: BTreeSet<&PathBuf> = base.keys().chain(theirs.keys()).collect();
+    for rel_path in paths {
+        if *rel_path == answers_file {
+            continue;
+        }
+        apply_file(
+            project_dir,
+            rel_path,
+            base.get(rel_path).map(Vec::as_slice),
+            theirs.get(rel_path).map(Vec::as_slice),
+            &mut report,
+        )?;
+    }
+
+    write_answers(
+        project_dir,
+        &new_resolved.config,
+        &new_variables,
+        &TemplateOrigin::Git {
+            url: url.to_string(),
+            git_ref,
+            commit_sha: new_clone.commit_sha.clone(),
+            subpath: saved.template_subpath.clone(),
+        },
+        saved.format.as_deref(),
+    )?;
+
+    Ok(report)
+}
+
+fn resolve_at(
+    project_dir: &Path,
+    repo_dir: &Path,
+    subpath: Option<&str>,
+    format: Option<&str>,
+) -> Result<ResolvedTemplate> {
+    let template_dir = match subpath {
+        Some(sub) => repo_dir.join(sub),
+        None => repo_dir.to_path_buf(),
+    };
+    if !template_dir.exists() {
+        return Err(DicecutError::UpdateUnsupported {
+            path: project_dir.to_path_buf(),
+            reason: format!(
+                "the template subpath '{}' does not exist in the repository",
+                subpath.unwrap_or_default()
+            ),
+        });
+    }
+    resolve_template_as(&template_dir, format)
+}
+
+fn render_files(
+    resolved: &ResolvedTemplate,
+    variables: &BTreeMap<String, tera::Value>,
+) -> Result<BTreeMap<PathBuf, Vec<u8>>> {
+    let context = resolved.render_context(variables);
+    let plan = plan_render(resolved, variables, &context)?;
+    Ok(plan
+        .files
+        .into_iter()
+        .map(|f| (f.relative_path, f.content))
+        .collect())
+}
+
+/// Reconcile one file given its previous template version (`base`), its new
+/// template version (`theirs`), and whatever is currently in the project.
+fn apply_file(
+    project_dir: &Path,
+    rel_path: &Path,
+    base: Option<&[u8]>,
+    theirs: Option<&[u8]>,
+    report: &mut UpdateReport,
+) -> Result<()> {
+    let dest = project_dir.join(rel_path);
+    let ours = if dest.is_file() {
+        Some(std::fs::read(&dest).map_err(|e| DicecutError::Io {
+            context: format!("reading {}", dest.display()),
+            source: e,
+        })?)
+    } else {
+        None
+    };
+
+    match (base, theirs, ours.as_deref()) {
+        // Dropped by the template: remove unless it was edited locally.
+        (Some(base), None, Some(ours)) => {
+            if ours == base {
+                std::fs::remove_file(&dest).map_err(|e| DicecutError::Io {
+                    context: format!("removing {}", dest.display()),
+                    source: e,
+                })?;
+                report.removed.push(rel_path.to_path_buf());
+            } else {
+                report.kept.push(rel_path.to_path_buf());
+            }
+        }
+        // New in the template and absent locally.
+        (None, Some(theirs), None) => {
+            write_file(&dest, theirs)?;
+            report.updated.push(rel_path.to_path_buf());
+        }
+        // New in the template but a different file already exists locally.
+        (None, Some(theirs), Some(ours)) => {
+            if ours != theirs {
+                write_file(&rejected_path(&dest), theirs)?;
+                report.rejected.push(rel_path.to_path_buf());
+            }
+        }
+        (Some(base), Some(theirs), Some(ours)) => {
+            if theirs == base || ours == theirs {
+                return Ok(());
+            }
+            if ours == base {
+                write_file(&dest, theirs)?;
+                report.updated.push(rel_path.to_path_buf());
+                return Ok(());
+            }
+            let is_text = std::str::from_utf8(base).is_ok()
+                && std::str::from_utf8(ours).is_ok()
+                && std::str::from_utf8(theirs).is_ok();
+            if !is_text {
+                write_file(&rejected_path(&dest), theirs)?;
+                report.rejected.push(rel_path.to_path_buf());
+                return Ok(());
+            }
+            match merge_text(rel_path, base, ours, theirs)? {
+                MergeOutcome::Clean(merged) => {
+                    write_file(&dest, &merged)?;
+                    report.updated.push(rel_path.to_path_buf());
+                }
+                MergeOutcome::Conflicted(merged) => {
+                    write_file(&dest, &merged)?;
+                    report.conflicts.push(rel_path.to_path_buf());
+                }
+            }
+        }
+        // Deleted locally, or never part of either template version.
+        (Some(_), Some(_), None) | (_, None, None) | (None, None, Some(_)) => {}
+    }
+
+    Ok(())
+}
+
+fn rejected_path(dest: &Path) -> PathBuf {
+    let mut name = OsString::from(dest.as_os_str());
+    name.push(".rej");
+    PathBuf::from(name)
+}
+
+fn write_file(dest: &Path, content: &[u8]) -> Result<()> {
+    if let Some(parent) = dest.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| DicecutError::Io {
+            context: format!("creating directory {}", parent.display()),
+            source: e,
+        })?;
+    }
+    std::fs::write(dest, content).map_err(|e| DicecutError::Io {
+        context: format!("writing {}", dest.display()),
+        source: e,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_support::git;
+    use std::fs;
+
+    fn commit_template(repo: &Path, readme: &str, extra: Option<&str>) -> String {
+        fs::write(
+            repo.join("diecut.toml"),
+            r#"
+[template]
+name = "update-test"
+
+[variables.project_name]
+type = "string"
+default = "demo"
+"#,
+        )
+        .unwrap();
+        fs::create_dir_all(repo.join("template")).unwrap();
+        fs::write(repo.join("template/README.md.die"), readme).unwrap();
+        fs::write(repo.join("template/LICENSE"), "MIT\n").unwrap();
+        match extra {
+            Some(content) => fs::write(repo.join("template/CHANGELOG.md"), content).unwrap(),
+            None => {
+                let _ = fs::remove_file(repo.join("template/CHANGELOG.md"));
+            }
+        }
+        git(repo, &["add", "-A"]);
+        git(repo, &["commit", "--quiet", "-m", "template"]);
+        git(repo, &["rev-parse", "HEAD"])
+    }
+
+    #[test]
+    fn update_project_merges_template_changes() {
+        let repo = tempfile::tempdir().unwrap();
+        git(repo.path(), &["init", "--quiet"]);
+        let old_sha = commit_template(
+            repo.path(),
+            "# {{ project_name }}\n\nIntro.\n\nUsage.\n",
+            None,
+        );
+
+        let project = tempfile::tempdir().unwrap();
+        fs::write(
+            project.path().join("README.md"),
+            "# my-app\n\nIntro, edited locally.\n\nUsage.\n",
+        )
+        .unwrap();
+        fs::write(project.path().join("LICENSE"), "MIT\n").unwrap();
+        fs::write(
+            project.path().join(".diecut-answers.toml"),
+            format!(
+                "[_diecut]\ntemplate_source = \"{}\"\ncommit_sha = \"{old_sha}\"\n\n[variables]\nproject_name = \"my-app\"\n",
+                repo.path().display()
+            ),
+        )
+        .unwrap();
+
+        let new_sha = commit_template(
+            repo.path(),
+            "# {{ project_name }}\n\nIntro.\n\nUsage, improved.\n",
+            Some("# Changelog\n"),
+        );
+
+        let report = update_project(UpdateOptions {
+            project_dir: project.path().to_path_buf(),
+            git_ref: None,
+            defaults: true,
+            no_input: true,
+        })
+        .unwrap();
+
+        assert_eq!(report.new_commit.as_deref(), Some(new_sha.as_str()));
+        assert!(report.conflicts.is_empty());
+        assert_eq!(
+            fs::read_to_string(project.path().join("README.md")).unwrap(),
+            "# my-app\n\nIntro, edited locally.\n\nUsage, improved.\n"
+        );
+        assert_eq!(
+            fs::read_to_string(project.path().join("CHANGELOG.md")).unwrap(),
+            "# Changelog\n"
+        );
+
+        let saved = load_answers(project.path()).unwrap();
+        assert_eq!(saved.commit_sha.as_deref(), Some(new_sha.as_str()));
+    }
+
+    #[test]
+    fn update_project_no_input_lists_new_variables() {
+        let repo = tempfile::tempdir().unwrap();
+        git(repo.path(), &["init", "--quiet"]);
+        let old_sha = commit_template(repo.path(), "# {{ project_name }}\n", None);
+
+        let project = tempfile::tempdir().unwrap();
+        fs::write(project.path().join("README.md"), "# my-app\n").unwrap();
+        fs::write(
+            project.path().join(".diecut-answers.toml"),
+            format!(
+                "[_diecut]\ntemplate_source = \"{}\"\ncommit_sha = \"{old_sha}\"\n\n[variables]\nproject_name = \"my-app\"\n",
+                repo.path().display()
+            ),
+        )
+        .unwrap();
+
+        fs::write(
+            repo.path().join("diecut.toml"),
+            "[template]\nname = \"update-test\"\n\n[variables.project_name]\ntype = \"string\"\n\n[variables.owner]\ntype = \"string\"\n",
+        )
+        .unwrap();
+        git(repo.path(), &["commit", "--quiet", "-am", "add owner"]);
+
+        let result = update_project(UpdateOptions {
+            project_dir: project.path().to_path_buf(),
+            git_ref: None,
+            defaults: false,
+            no_input: true,
+        });
+        match result {
+            Err(DicecutError::MissingValues { missing }) => assert!(missing.contains("owner")),
+            other => panic!("expected MissingValues, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn update_project_requires_git_origin() {
+        let project = tempfile::tempdir().unwrap();
+        fs::write(
+            project.path().join(".diecut-answers.toml"),
+            "[_diecut]\ntemplate = \"local\"\n\n[variables]\n",
+        )
+        .unwrap();
+
+        let result = update_project(UpdateOptions {
+            project_dir: project.path().to_path_buf(),
+            git_ref: None,
+            defaults: true,
+            no_input: true,
+        });
+        assert!(matches!(
+            result,
+            Err(DicecutError::UpdateUnsupported { .. })
+        ));
+    }
+
+    #[test]
+    fn apply_file_removes_unmodified_dropped_file() {
+        let project = tempfile::tempdir().unwrap();
+        fs::write(project.path().join("old.txt"), "old").unwrap();
+        let mut report = UpdateReport::default();
+
+        apply_file(
+            project.path(),
+            Path::new("old.txt"),
+            Some(b"old"),
+            None,
+            &mut report,
+        )
+        .unwrap();
+
+        assert!(!project.path().join("old.txt").exists());
+        assert_eq!(report.removed, vec![PathBuf::from("old.txt")]);
+    }
+
+    #[test]
+    fn apply_file_keeps_modified_dropped_file() {
+        let project = tempfile::tempdir().unwrap();
+        fs::write(project.path().join("old.txt"), "edited").unwrap();
+        let mut report = UpdateReport::default();
+
+        apply_file(
+            project.path(),
+            Path::new("old.txt"),
+            Some(b"old"),
+            None,
+            &mut report,
+        )
+        .unwrap();
+
+        assert!(project.path().join("old.txt").exists());
+        assert_eq!(report.kept, vec![PathBuf::from("old.txt")]);
+    }
+
+    #[test]
+    fn apply_file_writes_rej_for_binary_conflict() {
+        let project = tempfile::tempdir().unwrap();
+        fs::write(project.path().join("logo.png"), b"\x89PNG\x00local").unwrap();
+        let mut report = UpdateReport::default();
+
+        apply_file(
+            project.path(),
+            Path::new("logo.png"),
+            Some(b"\x89PNG\x00base"),
+            Some(b"\x89PNG\x00new"),
+            &mut report,
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read(project.path().join("logo.png")).unwrap(),
+            b"\x89PNG\x00local"
+        );
+        assert_eq!(
+            fs::read(project.path().join("logo.png.rej")).unwrap(),
+            b"\x89PNG\x00new"
+        );
+        assert_eq!(report.rejected, vec![PathBuf::from("logo.png")]);
+    }
+}
@@ -60,6 +60,76 @@ prompt = "Pick one"
     assert!(result.is_err());
 }
 
+#[rstest]
+#[case("type = \"int\"\nmin = 10\nmax = 1", "'min' is greater than 'max'")]
+#[case(
+    "type = \"string\"\nmin = 1",
+    "'min' only applies to int and float variables"
+)]
+#[case(
+    "type = \"int\"\nmin_items = 1",
+    "'min_items' only applies to multiselect, list, and group variables"
+)]
+#[case("type = \"int\"\nmin = 1\ndefault = 0", "default 0 must be at least 1")]
+#[case(
+    "type = \"string\"\nmax_length = 3\ndefault = \"toolong\"",
+    "default \"toolong\" must be at most 3 characters"
+)]
+#[case(
+    "type = \"multiselect\"\nchoices = [\"a\", \"b\"]\nmin_items = 1\ndefault = []",
+    "default [] must select at least 1 items"
+)]
+fn test_config_validation_constraints(#[case] variable: &str, #[case] expected: &str) {
+    let toml_str = format!("[template]\nname = \"bad\"\n\n[variables.v]\n{variable}\n");
+    let config: diecut::config::schema::TemplateConfig = toml::from_str(&toml_str).unwrap();
+    match config.validate() {
+        Err(diecut::error::DicecutError::ConfigInvalidVariable { name, reason }) => {
+            assert_eq!(name, "v");
+            assert_eq!(reason, expected);
+        }
+        other => panic!("expected an invalid variable error, got {other:?}"),
+    }
+}
+
+#[rstest]
+#[case(
+    "type = \"select\"\nchoices = [\"a\"]\nchoices_from = \"{{ x }}\"",
+    "use either 'choices' or 'choices_from', not both"
+)]
+#[case(
+    "type = \"string\"\nchoices_from = \"{{ x }}\"",
+    "'choices_from' only applies to select and multiselect variables"
+)]
+#[case(
+    "type = \"select\"\nchoices = [\"a\", { value = \"a\", label = \"A\" }]",
+    "choice 'a' is listed twice"
+)]
+fn test_config_validation_choices(#[case] variable: &str, #[case] expected: &str) {
+    let toml_str = format!("[template]\nname = \"bad\"\n\n[variables.v]\n{variable}\n");
+    let config: diecut::config::schema::TemplateConfig = toml::from_str(&toml_str).unwrap();
+    match config.validate() {
+        Err(diecut::error::DicecutError::ConfigInvalidVariable { reason, .. }) => {
+            assert_eq!(reason, expected)
+        }
+        other => panic!("expected an invalid variable error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_config_validation_rejects_bad_choice_when() {
+    let toml_str = r#"
+[template]
+name = "bad"
+
+[variables.v]
+type = "select"
+choices = [{ value = "a", when = "(" }]
+"#;
+    let config: diecut::config::schema::TemplateConfig = toml::from_str(toml_str).unwrap();
+    let err = config.validate().unwrap_err().to_string();
+    assert!(err.contains("`when` of choice 'a'"), "{err}");
+}
+
 #[test]
 fn test_generate_basic_template() {
     let template_dir = fixture_path("basic-template");
@@ -168,6 +238,7 @@ fn test_prompt_options_with_data_overrides() {
         .into_iter()
         .collect(),
         use_defaults: false,
+        ..Default::default()
     };
 
     let config = load_config(&fixture_path("basic-template")).unwrap();
@@ -189,6 +260,7 @@ fn test_prompt_options_with_defaults() {
     let options = PromptOptions {
         data_overrides: std::collections::HashMap::new(),
         use_defaults: true,
+        ..Default::default()
     };
 
     let config = load_config(&fixture_path("basic-template")).unwrap();
@@ -224,6 +296,7 @@ fn test_answers_file_written() {
         &resolved.config,
         &variables,
         &diecut::answers::TemplateOrigin::Local,
+        None,
     )
     .unwrap();
 
@@ -354,6 +427,682 @@ name = "new-style"
     );
 }
 
+fn version_config(min: Option<&str>, max: Option<&str>) -> diecut::config::schema::TemplateConfig {
+    let mut toml_str = String::from("[template]\nname = \"versioned\"\n");
+    if let Some(min) = min {
+        toml_str.push_str(&format!("min_diecut_version = \"{min}\"\n"));
+    }
+    if let Some(max) = max {
+        toml_str.push_str(&format!("max_diecut_version = \"{max}\"\n"));
+    }
+    toml::from_str(&toml_str).unwrap()
+}
+
+#[rstest]
+#[case(None, None, true)]
+#[case(Some("0.3.0"), None, true)]
+#[case(Some("0.3"), None, true)]
+#[case(Some("v0.3.6"), Some("0.3.6"), true)]
+#[case(Some("0.4.0"), None, false)]
+#[case(Some("1"), None, false)]
+#[case(None, Some("0.3.5"), false)]
+#[case(None, Some("0.4"), true)]
+#[case(None, Some("0.3"), true)]
+#[case(None, Some("0"), true)]
+#[case(None, Some("0.2"), false)]
+fn test_check_diecut_version(
+    #[case] min: Option<&str>,
+    #[case] max: Option<&str>,
+    #[case] compatible: bool,
+) {
+    let result = version_config(min, max).check_diecut_version("0.3.6");
+    assert_eq!(result.is_ok(), compatible, "{result:?}");
+    if !compatible {
+        assert!(matches!(
+            result,
+            Err(diecut::error::DicecutError::IncompatibleDiecutVersion { .. })
+        ));
+    }
+}
+
+#[rstest]
+#[case("0.4", "0.4.0", true)]
+#[case("0.4", "0.4.1", true)]
+#[case("0.4", "0.5.0", false)]
+#[case("0", "0.9.3", true)]
+#[case("0", "1.0.0", false)]
+#[case("0.4.0", "0.4.1", false)]
+fn test_partial_max_diecut_version_allows_the_series(
+    #[case] max: &str,
+    #[case] current: &str,
+    #[case] compatible: bool,
+) {
+    let result = version_config(None, Some(max)).check_diecut_version(current);
+    assert_eq!(result.is_ok(), compatible, "{result:?}");
+}
+
+#[test]
+fn test_check_diecut_version_rejects_malformed_version() {
+    let result = version_config(Some("latest"), None).check_diecut_version("0.3.6");
+    assert!(matches!(
+        result,
+        Err(diecut::error::DicecutError::InvalidDiecutVersion { field, .. }) if field == "min_diecut_version"
+    ));
+}
+
+#[test]
+fn test_resolve_template_requires_newer_diecut() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(
+        tmp.path().join("diecut.toml"),
+        "[template]\nname = \"future\"\nmin_diecut_version = \"999.0.0\"\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(tmp.path().join("template")).unwrap();
+
+    let err = adapter::resolve_template(tmp.path()).err().unwrap();
+    let help = miette::Diagnostic::help(&err).unwrap().to_string();
+    assert!(help.contains("999.0.0"), "{help}");
+}
+
+#[test]
+fn test_deprecated_template_warns() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::write(
+        tmp.path().join("diecut.toml"),
+        "[template]\nname = \"old\"\ndeprecated = \"use gh:org/new-template instead\"\n",
+    )
+    .unwrap();
+    std::fs::create_dir_all(tmp.path().join("template")).unwrap();
+
+    let resolved = adapter::resolve_template(tmp.path()).unwrap();
+    assert_eq!(resolved.warnings.len(), 1);
+    assert!(resolved.warnings[0].contains("use gh:org/new-template instead"));
+}
+
+// --- Template inheritance ---
+
+fn write_files(root: &std::path::Path, files: &[(&str, &str)]) {
+    for (path, content) in files {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+}
+
+#[test]
+fn test_extends_merges_config_and_overlays_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_files(
+        &tmp.path().join("base"),
+        &[
+            (
+                "diecut.toml",
+                r#"
+[template]
+name = "base"
+
+[variables.name]
+type = "string"
+default = "svc"
+
+[variables.port]
+type = "int"
+default = 8080
+
+[files]
+exclude = ["*.bak"]
+
+[hooks]
+post_create = "make setup"
+"#,
+            ),
+            ("template/README.md.die", "base readme for {{ name }}"),
+            ("template/Makefile", "all:"),
+            ("template/old.bak", "ignored"),
+        ],
+    );
+    write_files(
+        &tmp.path().join("child"),
+        &[
+            (
+                "diecut.toml",
+                r#"
+[template]
+name = "child"
+extends = "../base"
+
+[variables.port]
+type = "int"
+default = 9090
+
+[variables.team]
+type = "string"
+default = "payments"
+
+[files]
+exclude = ["*.tmp"]
+"#,
+            ),
+            ("template/README.md", "child readme"),
+            ("template/src/main.rs.die", "// {{ team }} on {{ port }}"),
+        ],
+    );
+
+    let resolved = adapter::resolve_template(&tmp.path().join("child")).unwrap();
+    let config = &resolved.config;
+    assert_eq!(config.template.name, "child");
+    assert_eq!(
+        config.variables.keys().collect::<Vec<_>>(),
+        vec!["name", "port", "team"]
+    );
+    assert_eq!(
+        config.variables["port"].default,
+        Some(toml::Value::Integer(9090))
+    );
+    assert_eq!(config.files.exclude, vec!["*.bak", "*.tmp"]);
+    assert_eq!(config.hooks.post_create.as_deref(), Some("make setup"));
+    assert_eq!(resolved.content_dirs.len(), 2);
+
+    let variables = BTreeMap::from([
+        ("name".to_string(), tera::Value::from("svc")),
+        ("port".to_string(), tera::Value::from(9090)),
+        ("team".to_string(), tera::Value::from("payments")),
+    ]);
+    let plan = plan_render(&resolved, &variables, &build_context(&variables)).unwrap();
+    let mut outputs: Vec<(String, String)> = plan
+        .files
+        .iter()
+        .map(|f| {
+            (
+                f.relative_path.to_string_lossy().replace('\\', "/"),
+                String::from_utf8_lossy(&f.content).into_owned(),
+            )
+        })
+        .collect();
+    outputs.sort();
+    assert_eq!(
+        outputs,
+        vec![
+            ("Makefile".to_string(), "all:".to_string()),
+            ("README.md".to_string(), "child readme".to_string()),
+            ("src/main.rs".to_string(), "// payments on 9090".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn test_extends_cycle_is_reported() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_files(
+        &tmp.path().join("a"),
+        &[(
+            "diecut.toml",
+            "[template]\nname = \"a\"\nextends = \"../b\"\n",
+        )],
+    );
+    write_files(
+        &tmp.path().join("b"),
+        &[(
+            "diecut.toml",
+            "[template]\nname = \"b\"\nextends = \"../a\"\n",
+        )],
+    );
+
+    let result = adapter::resolve_template(&tmp.path().join("a"));
+    assert!(matches!(
+        result,
+        Err(diecut::error::DicecutError::ExtendsCycle { .. })
+    ));
+}
+
+#[test]
+fn test_extends_missing_base_errors() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_files(
+        tmp.path(),
+        &[(
+            "diecut.toml",
+            "[template]\nname = \"orphan\"\nextends = \"../does-not-exist\"\n",
+        )],
+    );
+
+    let result = adapter::resolve_template(tmp.path());
+    assert!(matches!(
+        result,
+        Err(diecut::error::DicecutError::ConfigNotFound { .. })
+    ));
+}
+
+// --- Shared partials ---
+
+#[test]
+fn test_partials_are_shared_and_not_emitted() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_files(
+        tmp.path(),
+        &[
+            (
+                "diecut.toml",
+                r#"
+[template]
+name = "partials"
+
+[variables.name]
+type = "string"
+
+[files]
+partials = ["_snippets/**"]
+"#,
+            ),
+            ("partials/header.txt", "// Copyright {{ name }}"),
+            (
+                "partials/macros.tera",
+                "{% macro shout(text) %}{{ text | upper }}{% endmacro shout %}",
+            ),
+            ("template/_snippets/ci.yml", "run: make {{ name }}"),
+            (
+                "template/main.rs.die",
+                "{% import \"macros.tera\" as m %}{% include \"header.txt\" %}\n{{ m::shout(text=name) }}",
+            ),
+            ("template/ci.yml.die", "{% include \"_snippets/ci.yml\" %}"),
+        ],
+    );
+
+    let resolved = adapter::resolve_template(tmp.path()).unwrap();
+    let variables = BTreeMap::from([("name".to_string(), tera::Value::from("acme"))]);
+    let plan = plan_render(&resolved, &variables, &build_context(&variables)).unwrap();
+
+    let mut outputs: Vec<(String, String)> = plan
+        .files
+        .iter()
+        .map(|f| {
+            (
+                f.relative_path.to_string_lossy().into_owned(),
+                String::from_utf8_lossy(&f.content).into_owned(),
+            )
+        })
+        .collect();
+    outputs.sort();
+    assert_eq!(
+        outputs,
+        vec![
+            ("ci.yml".to_string(), "run: make acme".to_string()),
+            ("main.rs".to_string(), "// Copyright acme\nACME".to_string()),
+        ]
+    );
+
+    let report = diecut::check::check_template(tmp.path()).unwrap();
+    assert!(report.findings.is_empty(), "{:?}", report.findings);
+}
+
+#[test]
+fn test_child_partial_overrides_base_partial() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_files(
+        &tmp.path().join("base"),
+        &[
+            ("diecut.toml", "[template]\nname = \"base\"\n"),
+            ("partials/license.txt", "MIT"),
+            ("template/LICENSE.die", "{% include \"license.txt\" %}"),
+        ],
+    );
+    write_files(
+        &tmp.path().join("child"),
+        &[
+            (
+                "diecut.toml",
+                "[template]\nname = \"child\"\nextends = \"../base\"\n",
+            ),
+            ("partials/license.txt", "Apache-2.0"),
+        ],
+    );
+
+    let resolved = adapter::resolve_template(&tmp.path().join("child")).unwrap();
+    let variables = BTreeMap::new();
+    let plan = plan_render(&resolved, &variables, &build_context(&variables)).unwrap();
+    assert_eq!(plan.files.len(), 1);
+    assert_eq!(plan.files[0].content, b"Apache-2.0");
+}
+
+#[test]
+fn test_child_each_rule_loops_over_base_variable() {
+    let tmp = tempfile::tempdir().unwrap();
+    write_files(
+        &tmp.path().join("base"),
+        &[(
+            "diecut.toml",
+            "[template]\nname = \"base\"\n\n[variables.models]\ntype = \"list\"\ndefault = [\"user\", \"post\"]\n",
+        )],
+    );
+    write_files(
+        &tmp.path().join("child"),
+        &[
+            (
+                "diecut.toml",
+                r#"[template]
+name = "child"
+extends = "../base"
+
+[[files.each]]
+pattern = "models/{{ model }}.py.die"
+over = "models"
+as = "model"
+"#,
+            ),
+            (
+                "template/models/{{ model }}.py.die",
+                "class {{ model }}: pass\n",
+            ),
+        ],
+    );
+
+    let resolved = adapter::resolve_template(&tmp.path().join("child")).unwrap();
+    let variables = BTreeMap::from([("models".to_string(), serde_json::json!(["user", "post"]))]);
+    let plan = plan_render(&resolved, &variables, &build_context(&variables)).unwrap();
+    let mut paths: Vec<String> = plan
+        .files
+        .iter()
+        .map(|f| f.relative_path.display().to_string())
+        .collect();
+    paths.sort();
+    assert_eq!(paths, vec!["models/post.py", "models/user.py"]);
+
+    // The merged config is still checked: `as` may not hide a base variable.
+    let child_toml = tmp.path().join("child/diecut.toml");
+    let toml = std::fs::read_to_string(&child_toml).unwrap();
+    std::fs::write(
+        &child_toml,
+        toml.replace("as = \"model\"", "as = \"models\""),
+    )
+    .unwrap();
+    assert!(matches!(
+        adapter::resolve_template(&tmp.path().join("child")),
+        Err(diecut::error::DicecutError::InvalidEachRule { .. })
+    ));
+}
+
+#[test]
+fn test_generate_cookiecutter_template() {
+    let tmp = tempfile::tempdir().unwrap();
+    let template_dir = tmp.path().join("cc-template");
+    write_files(
+        &template_dir,
+        &[
+            (
+                "cookiecutter.json",
+                r#"{
+    "project_name": "My Tool",
+    "project_slug": "{{ cookiecutter.project_name | slugify }}",
+    "license": ["MIT", "Apache-2.0"],
+    "_copy_without_render": ["*.txt"]
+}"#,
+            ),
+            (
+                "{{cookiecutter.project_slug}}/README.md",
+                "# {{ cookiecutter.project_name }} ({{ cookiecutter.license }})\n",
+            ),
+            (
+                "{{cookiecutter.project_slug}}/{{cookiecutter.project_slug}}/main.py",
+                "print('{{ project_slug }}')\n",
+            ),
+            ("{{cookiecutter.project_slug}}/raw.txt", "{{ untouched }}"),
+            ("README.md", "Not part of the generated project"),
+        ],
+    );
+
+    let output = tmp.path().join("out");
+    diecut::generate(diecut::GenerateOptions {
+        template: Some(template_dir.display().to_string()),
+        output: Some(output.display().to_string()),
+        data: vec![("license".to_string(), "Apache-2.0".to_string())],
+        no_input: true,
+        no_hooks: true,
+        ..Default::default()
+    })
+    .unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(output.join("README.md")).unwrap(),
+        "# My Tool (Apache-2.0)\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(output.join("my-tool/main.py")).unwrap(),
+        "print('my-tool')\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(output.join("raw.txt")).unwrap(),
+        "{{ untouched }}"
+    );
+    let answers = std::fs::read_to_string(output.join(".diecut-answers.toml")).unwrap();
+    assert!(answers.contains("project_slug = \"my-tool\""), "{answers}");
+}
+
+#[test]
+fn test_cookiecutter_html_is_not_escaped() {
+    let tmp = tempfile::tempdir().unwrap();
+    let template_dir = tmp.path().join("cc-template");
+    write_files(
+        &template_dir,
+        &[
+            (
+                "cookiecutter.json",
+                r#"{"author": "Tom & Jerry <tj@x.io>"}"#,
+            ),
+            (
+                "{{cookiecutter.author | slugify}}/index.html",
+                "<p>{{ cookiecutter.author }}</p>\n",
+            ),
+        ],
+    );
+
+    let output = tmp.path().join("out");
+    diecut::generate(diecut::GenerateOptions {
+        template: Some(template_dir.display().to_string()),
+        output: Some(output.display().to_string()),
+        no_input: true,
+        no_hooks: true,
+        ..Default::default()
+    })
+    .unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(output.join("index.html")).unwrap(),
+        "<p>Tom & Jerry <tj@x.io></p>\n"
+    );
+}
+
+#[test]
+fn test_generate_copier_template() {
+    let tmp = tempfile::tempdir().unwrap();
+    let template_dir = tmp.path().join("copier-template");
+    write_files(
+        &template_dir,
+        &[
+            (
+                "copier.yml",
+                r#"
+_exclude: ["*.bak"]
+_tasks: ["git init"]
+project_name:
+  type: str
+  default: demo
+  validator: "{% if project_name is not matching('^[a-z]+$') %}lowercase only{% endif %}"
+use_docker: false
+"#,
+            ),
+            (
+                "README.md.jinja",
+                "# {{ project_name }} ({{ _copier_conf.answers_file }})\n",
+            ),
+            (
+                "{% if use_docker %}Dockerfile{% endif %}.jinja",
+                "FROM scratch\n",
+            ),
+            ("notes.bak", "scratch"),
+            ("LICENSE", "{{ not rendered }}"),
+        ],
+    );
+
+    let resolved = adapter::resolve_template(&template_dir).unwrap();
+    assert_eq!(
+        resolved.warnings,
+        vec!["Copier option '_tasks' is not supported and was ignored"]
+    );
+
+    let output = tmp.path().join("out");
+    diecut::generate(diecut::GenerateOptions {
+        template: Some(template_dir.display().to_string()),
+        output: Some(output.display().to_string()),
+        no_input: true,
+        no_hooks: true,
+        ..Default::default()
+    })
+    .unwrap();
+
+    let mut files: Vec<String> = std::fs::read_dir(&output)
+        .unwrap()
+        .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    files.sort();
+    assert_eq!(files, vec![".diecut-answers.toml", "LICENSE", "README.md"]);
+    assert_eq!(
+        std::fs::read_to_string(output.join("README.md")).unwrap(),
+        "# demo (.diecut-answers.toml)\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(output.join("LICENSE")).unwrap(),
+        "{{ not rendered }}"
+    );
+
+    let rejected = diecut::plan_generation(diecut::GenerateOptions {
+        template: Some(template_dir.display().to_string()),
+        output: Some(tmp.path().join("rejected").display().to_string()),
+        data: vec![("project_name".to_string(), "Demo".to_string())],
+        no_input: true,
+        no_hooks: true,
+        ..Default::default()
+    });
+    match rejected {
+        Err(diecut::error::DicecutError::ValidationFailed { message, .. }) => {
+            assert_eq!(message, "lowercase only")
+        }
+        Err(other) => panic!("expected the validator to reject the value, got {other:?}"),
+        Ok(_) => panic!("expected the validator to reject the value"),
+    }
+}
+
+/// An in-house format: `vars.txt` holds one `name=default` per line, files live in `files/`.
+struct LineAdapter;
+
+impl adapter::TemplateAdapter for LineAdapter {
+    fn name(&self) -> &str {
+        "lines"
+    }
+
+    fn detect(&self, template_dir: &std::path::Path) -> bool {
+        template_dir.join("vars.txt").is_file()
+    }
+
+    fn resolve(
+        &self,
+        template_dir: &std::path::Path,
+    ) -> diecut::error::Result<adapter::ResolvedTemplate> {
+        let mut config = diecut::config::schema::TemplateConfig::default();
+        config.template.name = "lines".into();
+        config.template.templates_suffix = Some(String::new());
+        let vars = std::fs::read_to_string(template_dir.join("vars.txt")).unwrap();
+        for line in vars.lines() {
+            let (name, default) = line.split_once('=').unwrap();
+            config.variables.insert(
+                name.to_string(),
+                diecut::config::variable::VariableConfig {
+                    default: Some(toml::Value::String(default.to_string())),
+                    ..Default::default()
+                },
+            );
+        }
+        Ok(adapter::ResolvedTemplate {
+            config,
+            content_dirs: vec![template_dir.join("files")],
+            partials_dirs: Vec::new(),
+            warnings: Vec::new(),
+            namespace: None,
+            globals: Default::default(),
+            format: self.name().to_string(),
+            skip_empty_paths: false,
+        })
+    }
+}
+
+#[test]
+fn test_registered_adapter_and_format_override() {
+    adapter::register_adapter(LineAdapter);
+
+    let tmp = tempfile::tempdir().unwrap();
+    let template_dir = tmp.path().join("line-template");
+    write_files(
+        &template_dir,
+        &[
+            ("vars.txt", "greeting=hello"),
+            ("files/hello.txt", "{{ greeting }} world"),
+            ("cookiecutter.json", r#"{"greeting": "hi"}"#),
+            (
+                "{{cookiecutter.greeting}}/hello.txt",
+                "{{ greeting }} there",
+            ),
+        ],
+    );
+    assert_eq!(
+        adapter::detect_format(&template_dir).as_deref(),
+        Some("lines")
+    );
+
+    let render = |format: Option<&str>| {
+        let plan = diecut::plan_generation(diecut::GenerateOptions {
+            template: Some(template_dir.display().to_string()),
+            format: format.map(String::from),
+            output: Some(tmp.path().join("out").display().to_string()),
+            no_input: true,
+            no_hooks: true,
+            ..Default::default()
+        })
+        .unwrap();
+        String::from_utf8(plan.render_plan.files[0].content.clone()).unwrap()
+    };
+    assert_eq!(render(None), "hello world");
+    assert_eq!(render(Some("cookiecutter")), "hi there");
+
+    // The forced format is recorded, so replaying reads the template the same way.
+    let project = tmp.path().join("project");
+    diecut::generate(diecut::GenerateOptions {
+        template: Some(template_dir.display().to_string()),
+        format: Some("cookiecutter".to_string()),
+        output: Some(project.display().to_string()),
+        no_input: true,
+        no_hooks: true,
+        ..Default::default()
+    })
+    .unwrap();
+    let answers = std::fs::read_to_string(project.join(".diecut-answers.toml")).unwrap();
+    assert!(answers.contains("format = \"cookiecutter\""), "{answers}");
+    let replayed = diecut::plan_generation(diecut::GenerateOptions {
+        template: Some(template_dir.display().to_string()),
+        answers_file: Some(project.clone()),
+        output: Some(tmp.path().join("replayed").display().to_string()),
+        no_input: true,
+        no_hooks: true,
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(replayed.render_plan.files[0].content, b"hi there");
+
+    let report = diecut::check::check_template(&template_dir).unwrap();
+    assert_eq!(report.format, "lines");
+    let report = diecut::check::check_template_as(&template_dir, Some("cookiecutter")).unwrap();
+    assert_eq!(report.format, "cookiecutter");
+}
+
 // --- Edge case: unsupported template format ---
 
 #[test]
@@ -413,6 +1162,233 @@ fn test_render_with_special_characters() {
     );
 }
 
+#[test]
+fn test_plan_generation_reads_data_file() {
+    let tmp = tempfile::tempdir().unwrap();
+    let data_file = tmp.path().join("answers.yaml");
+    std::fs::write(
+        &data_file,
+        "project_name: from-file\nuse_docker: true\nlicense: Apache-2.0\n",
+    )
+    .unwrap();
+
+    let options = diecut::GenerateOptions {
+        template: Some(fixture_path("basic-template").display().to_string()),
+        output: Some(tmp.path().join("out").display().to_string()),
+        data: vec![("project_name".to_string(), "from-flag".to_string())],
+        data_file: Some(data_file),
+        no_input: true,
+        no_hooks: true,
+        ..Default::default()
+    };
+    let plan = diecut::plan_generation(options).unwrap();
+
+    assert_eq!(plan.variables["project_name"], "from-flag");
+    assert_eq!(plan.variables["use_docker"], tera::Value::Bool(true));
+    assert_eq!(plan.variables["license"], "Apache-2.0");
+    assert_eq!(plan.variables["author"], "Test Author");
+}
+
+#[test]
+fn test_group_variable_renders_and_replays() {
+    let tmp = tempfile::tempdir().unwrap();
+    let template_dir = tmp.path().join("template-src");
+    write_files(
+        &template_dir,
+        &[
+            (
+                "diecut.toml",
+                r#"
+[template]
+name = "services"
+
+[variables.services]
+type = "group"
+default = [{ name = "api", port = 8080 }, { name = "worker" }]
+
+[variables.services.fields.name]
+type = "string"
+
+[variables.services.fields.port]
+type = "int"
+default = 9000
+"#,
+            ),
+            (
+                "template/services.txt.die",
+                "{% for s in services %}{{ s.name }}:{{ s.port }}\n{% endfor %}",
+            ),
+        ],
+    );
+
+    let output = tmp.path().join("out");
+    diecut::generate(diecut::GenerateOptions {
+        template: Some(template_dir.display().to_string()),
+        output: Some(output.display().to_string()),
+        no_input: true,
+        no_hooks: true,
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(output.join("services.txt")).unwrap(),
+        "api:8080\nworker:9000\n"
+    );
+
+    let replayed = diecut::plan_generation(diecut::GenerateOptions {
+        template: Some(template_dir.display().to_string()),
+        output: Some(tmp.path().join("replay").display().to_string()),
+        answers_file: Some(output.clone()),
+        no_input: true,
+        no_hooks: true,
+        ..Default::default()
+    })
+    .unwrap();
+    assert_eq!(
+        replayed.variables["services"],
+        serde_json::json!([
+            {"name": "api", "port": 8080},
+            {"name": "worker", "port": 9000},
+        ])
+    );
+}
+
+#[rstest]
+#[case("type = \"table\"", "table/group variables must have 'fields' defined")]
+#[case(
+    "type = \"string\"\n[variables.v.fields.a]\ntype = \"string\"",
+    "'fields' only applies to table and group variables"
+)]
+#[case(
+    "type = \"group\"\n[variables.v.fields.a]\ntype = \"table\"",
+    "field 'a' can't be a table or group"
+)]
+fn test_config_validation_fields(#[case] variable: &str, #[case] expected: &str) {
+    let toml_str = format!("[template]\nname = \"bad\"\n\n[variables.v]\n{variable}\n");
+    let config: diecut::config::schema::TemplateConfig = toml::from_str(&toml_str).unwrap();
+    match config.validate() {
+        Err(diecut::error::DicecutError::ConfigInvalidVariable { reason, .. }) => {
+            assert_eq!(reason, expected)
+        }
+        other => panic!("expected an invalid variable error, got {other:?}"),
+    }
+}
+
+#[test]
+fn test_files_each_renders_one_file_per_element() {
+    let tmp = tempfile::tempdir().unwrap();
+    let template_dir = tmp.path().join("template-src");
+    write_files(
+        &template_dir,
+        &[
+            (
+                "diecut.toml",
+                r#"
+[template]
+name = "models"
+
+[variables.models]
+type = "list"
+default = ["user", "order"]
+
+[variables.handlers]
+type = "group"
+default = [{ name = "health" }]
+
+[variables.handlers.fields.name]
+type = "string"
+
+[[files.each]]
+pattern = "src/models/{{ item }}.rs.die"
+over = "models"
+
+[[files.each]]
+pattern = "src/handlers/*.rs.die"
+over = "handlers"
+as = "handler"
+"#,
+            ),
+            (
+                "template/src/models/{{ item }}.rs.die",
+                "pub struct {{ item | capitalize }};\n",
+            ),
+            (
+                "template/src/handlers/{{ handler.name }}.rs.die",
+                "// {{ handler.name }} of {{ models | length }} models\n",
+            ),
+        ],
+    );
+
+    let resolved = adapter::resolve_template(&template_dir).unwrap();
+    let mut variables = BTreeMap::new();
+    variables.insert("models".to_string(), serde_json::json!(["user", "order"]));
+    variables.insert(
+        "handlers".to_string(),
+        serde_json::json!([{"name": "health"}]),
+    );
+    let plan = plan_render(&resolved, &variables, &build_context(&variables)).unwrap();
+
+    let mut files: Vec<(String, String)> = plan
+        .files
+        .iter()
+        .map(|f| {
+            (
+                f.relative_path.to_string_lossy().replace('\\', "/"),
+                String::from_utf8_lossy(&f.content).into_owned(),
+            )
+        })
+        .collect();
+    files.sort();
+    assert_eq!(
+        files,
+        vec![
+            (
+                "src/handlers/health.rs".to_string(),
+                "// health of 2 models\n".to_string()
+            ),
+            (
+                "src/models/order.rs".to_string(),
+                "pub struct Order;\n".to_string()
+            ),
+            (
+                "src/models/user.rs".to_string(),
+                "pub struct User;\n".to_string()
+            ),
+        ]
+    );
+}
+
+#[rstest]
+#[case(
+    "over = \"missing\"",
+    "'over' names 'missing', which is not a variable"
+)]
+#[case(
+    "over = \"name\"",
+    "'name' is a string variable; 'over' needs a list, multiselect, or group"
+)]
+#[case(
+    "over = \"models\"\nas = \"a-b\"",
+    "'as' must be a plain name, not 'a-b'"
+)]
+#[case(
+    "over = \"models\"\nas = \"name\"",
+    "'as = \"name\"' would hide the variable of the same name"
+)]
+fn test_config_validation_files_each(#[case] rule: &str, #[case] expected: &str) {
+    let toml_str = format!(
+        "[template]\nname = \"bad\"\n\n[variables.name]\ntype = \"string\"\n\n\
+         [variables.models]\ntype = \"list\"\n\n[[files.each]]\npattern = \"*.die\"\n{rule}\n"
+    );
+    let config: diecut::config::schema::TemplateConfig = toml::from_str(&toml_str).unwrap();
+    match config.validate() {
+        Err(diecut::error::DicecutError::InvalidEachRule { reason, .. }) => {
+            assert_eq!(reason, expected)
+        }
+        other => panic!("expected an invalid each rule error, got {other:?}"),
+    }
+}
+
 // --- plan_generation dry-run tests ---
 
 #[test]
@@ -422,7 +1398,7 @@ fn test_plan_generation_dry_run_no_files_written() {
     let output_path = tmp.path().join("dry-run-output");
 
     let options = diecut::GenerateOptions {
-        template: template_dir.to_string_lossy().to_string(),
+        template: Some(template_dir.to_string_lossy().to_string()),
         output: Some(output_path.to_string_lossy().to_string()),
         data: vec![
             ("project_name".to_string(), "test-project".to_string()),
@@ -431,9 +1407,8 @@ fn test_plan_generation_dry_run_no_files_written() {
             ("license".to_string(), "MIT".to_string()),
         ],
         defaults: true,
-        overwrite: false,
         no_hooks: true,
-        protocol: diecut::template::GitProtocol::default(),
+        ..Default::default()
     };
 
     // plan_generation should succeed
@@ -571,7 +1546,7 @@ fn test_plan_generation_verbose_has_content() {
     let output_path = tmp.path().join("verbose-output");
 
     let options = diecut::GenerateOptions {
-        template: template_dir.to_string_lossy().to_string(),
+        template: Some(template_dir.to_string_lossy().to_string()),
         output: Some(output_path.to_string_lossy().to_string()),
         data: vec![
             ("project_name".to_string(), "test-project".to_string()),
@@ -580,9 +1555,8 @@ fn test_plan_generation_verbose_has_content() {
             ("license".to_string(), "MIT".to_string()),
         ],
         defaults: true,
-        overwrite: false,
         no_hooks: true,
-        protocol: diecut::template::GitProtocol::default(),
+        ..Default::default()
     };
 
     let plan = diecut::plan_generation(options).unwrap();
@@ -675,3 +1649,85 @@ default = "demo"
         "expected 'Would use local path:' in stdout, got: {stdout}"
     );
 }
+
+#[test]
+fn test_convert_cargo_generate_template() {
+    let tmp = tempfile::tempdir().unwrap();
+    let template_dir = tmp.path().join("cg-template");
+    write_files(
+        &template_dir,
+        &[
+            (
+                "cargo-generate.toml",
+                r#"
+[template]
+ignore = ["notes.md"]
+
+[placeholders.use-serde]
+type = "bool"
+prompt = "Use serde?"
+default = false
+
+[conditional.'crate_type == "lib"']
+ignore = ["src/main.rs"]
+"#,
+            ),
+            (
+                "Cargo.toml",
+                "[package]\nname = \"{{project-name}}\"\n{% if use-serde %}serde = \"1\"\n{% endif %}",
+            ),
+            ("src/main.rs", "fn main() {}\n"),
+            (
+                "src/lib.rs.liquid",
+                "//! {{ crate_name | upcase }}, {{ crate_name | pascal_case }}\n",
+            ),
+            ("notes.md", "Not copied"),
+        ],
+    );
+
+    let native_dir = tmp.path().join("native");
+    let report =
+        diecut::convert::convert_template(&template_dir, &native_dir, None, false).unwrap();
+    assert_eq!(report.format, "cargo-generate");
+    assert!(native_dir.join("template/Cargo.toml.die").is_file());
+    assert!(native_dir.join("template/src/main.rs").is_file());
+    assert!(native_dir.join("template/src/lib.rs.die").is_file());
+    assert!(!native_dir.join("template/notes.md").exists());
+    assert!(
+        report.notes.iter().any(|n| n.contains("'pascal_case'")),
+        "{:?}",
+        report.notes
+    );
+
+    // Replace the filter the report flagged, then generate from the result.
+    let lib = native_dir.join("template/src/lib.rs.die");
+    let fixed = std::fs::read_to_string(&lib)
+        .unwrap()
+        .replace(", {{ crate_name | pascal_case }}", "");
+    std::fs::write(&lib, fixed).unwrap();
+
+    let output = tmp.path().join("out");
+    diecut::generate(diecut::GenerateOptions {
+        template: Some(native_dir.display().to_string()),
+        output: Some(output.display().to_string()),
+        data: vec![
+            ("project_name".to_string(), "my-crate".to_string()),
+            ("crate_type".to_string(), "lib".to_string()),
+            ("use_serde".to_string(), "true".to_string()),
+        ],
+        no_input: true,
+        no_hooks: true,
+        ..Default::default()
+    })
+    .unwrap();
+
+    assert_eq!(
+        std::fs::read_to_string(output.join("Cargo.toml")).unwrap(),
+        "[package]\nname = \"my-crate\"\nserde = \"1\"\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(output.join("src/lib.rs")).unwrap(),
+        "//! MY_CRATE\n"
+    );
+    assert!(!output.join("src/main.rs").exists());
+}